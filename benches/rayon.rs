@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use rayon::prelude::*;
+use shared_vector::Vector;
+
+criterion_group!(rayon, map_sum);
+criterion_main!(rayon);
+
+fn heavy(x: u64) -> u64 {
+    let mut h = x;
+    for _ in 0..64 {
+        h = h.wrapping_mul(0x9E3779B97F4A7C15).rotate_left(17) ^ x;
+    }
+    h
+}
+
+fn map_sum(c: &mut Criterion) {
+    let mut g = c.benchmark_group("map_sum");
+
+    let item_count = 1_000_000;
+    let v: Vector<u64> = (0..item_count).collect();
+    g.bench_function(BenchmarkId::new("serial", item_count), |b| b.iter(|| black_box(v.iter().map(|x| heavy(*x)).fold(0u64, u64::wrapping_add))));
+    g.bench_function(BenchmarkId::new("parallel", item_count), |b| b.iter(|| black_box(v.par_iter().map(|x| heavy(*x)).reduce(|| 0, u64::wrapping_add))));
+}
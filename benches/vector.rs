@@ -1,7 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use shared_vector::{AtomicSharedVector, SharedVector, Vector};
+use shared_vector::{AtomicSharedVector, SharedVector, SmallVector, Vector, GrowthStrategy, DoublingGrowth, GoldenRatioGrowth, ExactGrowth};
+use shared_vector::alloc::{Global, PooledAllocator};
 
-criterion_group!(vector, vector_push);
+criterion_group!(vector, vector_push, vector_append, vector_shrink, vector_push_pop_small, vector_clone, vector_growth, vector_drain_into, vector_flatten, vector_pool, vector_delete, vector_batch_push, vector_collect_shared);
 criterion_main!(vector);
 
 fn push_shared(n: u32, initial_cap: usize) {
@@ -41,10 +42,232 @@ fn vector_push(c: &mut Criterion) {
 
     for item_count in [1000, 10_000] {
         for initial_cap in [1024, 256, 32] {
-            g.bench_with_input(BenchmarkId::new(&format!("shared({initial_cap})"), &item_count), &item_count, |b, item_count| b.iter (||push_shared(*item_count, black_box(initial_cap))));
-            g.bench_with_input(BenchmarkId::new(&format!("atomic({initial_cap})"), &item_count), &item_count, |b, item_count| b.iter (||push_atomic(*item_count, black_box(initial_cap))));
-            g.bench_with_input(BenchmarkId::new(&format!("unique({initial_cap})"), &item_count), &item_count, |b, item_count| b.iter(||push_unique(*item_count, black_box(initial_cap))));
-            g.bench_with_input(BenchmarkId::new(&format!("std({initial_cap})"), &item_count), &item_count, |b, item_count| b.iter(||push_std(*item_count, black_box(initial_cap))));
+            g.bench_with_input(BenchmarkId::new(format!("shared({initial_cap})"), item_count), &item_count, |b, item_count| b.iter (||push_shared(*item_count, black_box(initial_cap))));
+            g.bench_with_input(BenchmarkId::new(format!("atomic({initial_cap})"), item_count), &item_count, |b, item_count| b.iter (||push_atomic(*item_count, black_box(initial_cap))));
+            g.bench_with_input(BenchmarkId::new(format!("unique({initial_cap})"), item_count), &item_count, |b, item_count| b.iter(||push_unique(*item_count, black_box(initial_cap))));
+            g.bench_with_input(BenchmarkId::new(format!("std({initial_cap})"), item_count), &item_count, |b, item_count| b.iter(||push_std(*item_count, black_box(initial_cap))));
         }
     }
 }
+
+fn append_unique(n: u32) {
+    let mut a = Vector::with_capacity(n as usize);
+    let mut b = Vector::with_capacity(n as usize);
+    for i in 0..n {
+        b.push(i);
+    }
+    a.append(&mut b);
+    black_box(a);
+}
+
+fn extend_from_drain_unique(n: u32) {
+    let mut a = Vector::with_capacity(n as usize);
+    let mut b = Vector::with_capacity(n as usize);
+    for i in 0..n {
+        b.push(i);
+    }
+    a.extend(b.drain(..));
+    black_box(a);
+}
+
+fn vector_append(c: &mut Criterion) {
+    let mut g = c.benchmark_group("append");
+
+    let item_count = 1_000_000;
+    g.bench_with_input(BenchmarkId::new("append", item_count), &item_count, |b, item_count| b.iter(||append_unique(*item_count)));
+    g.bench_with_input(BenchmarkId::new("extend(drain)", item_count), &item_count, |b, item_count| b.iter(||extend_from_drain_unique(*item_count)));
+}
+
+fn shrink_unique(n: u32, remaining: usize) {
+    let mut v = Vector::with_capacity(n as usize);
+    for i in 0..n {
+        v.push(i);
+    }
+    v.truncate(remaining);
+    v.shrink_to_fit();
+    black_box(v);
+}
+
+fn vector_shrink(c: &mut Criterion) {
+    let mut g = c.benchmark_group("shrink");
+
+    // Shrinking a vector built to 1M items down to 10 items releases ~4MB.
+    let item_count = 1_000_000;
+    g.bench_with_input(BenchmarkId::new("shrink_to_fit", item_count), &item_count, |b, item_count| b.iter(||shrink_unique(*item_count, black_box(10))));
+}
+
+fn push_pop_small<const N: usize>(n: usize) {
+    let mut v: SmallVector<u32, N> = SmallVector::new();
+    for _ in 0..100 {
+        for i in 0..n {
+            v.push(i as u32);
+        }
+        for _ in 0..n {
+            black_box(v.pop());
+        }
+    }
+    black_box(v);
+}
+
+fn push_pop_std(n: usize) {
+    let mut v: Vec<u32> = Vec::new();
+    for _ in 0..100 {
+        for i in 0..n {
+            v.push(i as u32);
+        }
+        for _ in 0..n {
+            black_box(v.pop());
+        }
+    }
+    black_box(v);
+}
+
+fn vector_push_pop_small(c: &mut Criterion) {
+    let mut g = c.benchmark_group("push_pop_small");
+
+    g.bench_with_input(BenchmarkId::new("small(8)", 8), &8, |b, item_count| b.iter(||push_pop_small::<8>(*item_count)));
+    g.bench_with_input(BenchmarkId::new("std", 8), &8, |b, item_count| b.iter(||push_pop_std(*item_count)));
+    g.bench_with_input(BenchmarkId::new("small(64)", 64), &64, |b, item_count| b.iter(||push_pop_small::<64>(*item_count)));
+    g.bench_with_input(BenchmarkId::new("std", 64), &64, |b, item_count| b.iter(||push_pop_std(*item_count)));
+}
+
+fn vector_clone(c: &mut Criterion) {
+    let mut g = c.benchmark_group("clone");
+
+    let item_count = 10_000;
+    let src: Vector<u32> = (0..item_count).collect();
+    let mut dst = src.clone();
+    g.bench_function(BenchmarkId::new("clone", item_count), |b| b.iter(|| black_box(src.clone())));
+    g.bench_function(BenchmarkId::new("clone_from", item_count), |b| b.iter(|| {
+        dst.clone_from(&src);
+        black_box(&dst);
+    }));
+}
+
+fn push_growth<G: GrowthStrategy>(n: u32) {
+    let mut v: Vector<u32, Global, G> = Vector::new_in(Global);
+    for i in 0..n {
+        v.push(i);
+    }
+    black_box(v);
+}
+
+fn vector_growth(c: &mut Criterion) {
+    let mut g = c.benchmark_group("growth");
+
+    for item_count in [1000, 100_000] {
+        g.bench_with_input(BenchmarkId::new("doubling", item_count), &item_count, |b, item_count| b.iter(||push_growth::<DoublingGrowth>(*item_count)));
+        g.bench_with_input(BenchmarkId::new("golden_ratio", item_count), &item_count, |b, item_count| b.iter(||push_growth::<GoldenRatioGrowth>(*item_count)));
+    }
+    // Exact growth reallocates on every push, keep the item count low.
+    g.bench_with_input(BenchmarkId::new("exact", 1000), &1000, |b, item_count| b.iter(||push_growth::<ExactGrowth>(*item_count)));
+}
+
+fn drain_into(n: u64, bulk: bool) {
+    let mut src: Vector<u64> = (0..n).collect();
+    let mut dst: Vector<u64> = Vector::with_capacity(n as usize);
+    if bulk {
+        src.drain(..).collect_into_existing(&mut dst);
+    } else {
+        dst.extend(src.drain(..));
+    }
+    black_box((src, dst));
+}
+
+fn vector_drain_into(c: &mut Criterion) {
+    let mut g = c.benchmark_group("drain_into");
+
+    let item_count = 100_000;
+    g.bench_with_input(BenchmarkId::new("extend", item_count), &item_count, |b, item_count| b.iter(||drain_into(*item_count, false)));
+    g.bench_with_input(BenchmarkId::new("collect_into_existing", item_count), &item_count, |b, item_count| b.iter(||drain_into(*item_count, true)));
+}
+
+fn nested(outer: u32, inner: u32) -> Vector<Vector<u32>> {
+    (0..outer).map(|i| (0..inner).map(|j| i * inner + j).collect()).collect()
+}
+
+fn vector_flatten(c: &mut Criterion) {
+    let mut g = c.benchmark_group("flatten");
+
+    g.bench_function("flatten(1000x100)", |b| b.iter_batched(|| nested(1000, 100), |v| black_box(v.flatten()), criterion::BatchSize::LargeInput));
+    g.bench_function("flatten_into(1000x100)", |b| b.iter_batched(|| nested(1000, 100), |v| {
+        let mut dst = Vector::with_capacity(100_000);
+        v.flatten_into(&mut dst);
+        black_box(dst)
+    }, criterion::BatchSize::LargeInput));
+}
+
+fn build_and_drop<A: shared_vector::alloc::Allocator + Clone>(allocator: A) {
+    for _ in 0..100 {
+        let mut v: Vector<u64, A> = Vector::with_capacity_in(1024, allocator.clone());
+        for i in 0..1024 {
+            v.push(i);
+        }
+        black_box(v);
+    }
+}
+
+fn vector_pool(c: &mut Criterion) {
+    let mut g = c.benchmark_group("pool");
+
+    g.bench_function("global", |b| b.iter(|| build_and_drop(Global)));
+    g.bench_function("pooled", |b| b.iter(|| build_and_drop(PooledAllocator::new(4))));
+    PooledAllocator::drain_pool();
+}
+
+fn delete_range(n: u32, delete: bool) {
+    let mut v: Vector<Box<u32>> = (0..n).map(Box::new).collect();
+    for _ in 0..n / 20 {
+        if delete {
+            v.delete(1..11);
+        } else {
+            v.drain(1..11).for_each(drop);
+        }
+        black_box(&v);
+    }
+}
+
+fn vector_delete(c: &mut Criterion) {
+    let mut g = c.benchmark_group("delete");
+
+    let item_count = 10_000;
+    g.bench_with_input(BenchmarkId::new("drain", item_count), &item_count, |b, item_count| b.iter(||delete_range(*item_count, false)));
+    g.bench_with_input(BenchmarkId::new("delete", item_count), &item_count, |b, item_count| b.iter(||delete_range(*item_count, true)));
+}
+
+fn push_vec(src: Vec<i32>, batch: bool) {
+    let mut v = Vector::new();
+    if batch {
+        v.batch_push(src);
+    } else {
+        v.extend(src);
+    }
+    black_box(v);
+}
+
+fn vector_batch_push(c: &mut Criterion) {
+    let mut g = c.benchmark_group("batch_push");
+
+    let src: Vec<i32> = (0..100_000).collect();
+    g.bench_function("extend", |b| b.iter_batched(|| src.clone(), |src| push_vec(src, false), criterion::BatchSize::LargeInput));
+    g.bench_function("batch_push", |b| b.iter_batched(|| src.clone(), |src| push_vec(src, true), criterion::BatchSize::LargeInput));
+}
+
+fn shared_parts() -> Vec<SharedVector<u32>> {
+    (0..100).map(|i| SharedVector::from_slice(&[i; 1000])).collect()
+}
+
+fn vector_collect_shared(c: &mut Criterion) {
+    let mut g = c.benchmark_group("collect_shared");
+
+    g.bench_function("extend_loop(100x1000)", |b| b.iter_batched(shared_parts, |parts| {
+        let mut v = Vector::new();
+        for part in parts {
+            v.extend_from_slice(part.as_slice());
+        }
+        black_box(v.into_shared())
+    }, criterion::BatchSize::LargeInput));
+    g.bench_function("collect(100x1000)", |b| b.iter_batched(shared_parts, |parts| {
+        black_box(parts.into_iter().collect::<SharedVector<u32>>())
+    }, criterion::BatchSize::LargeInput));
+}
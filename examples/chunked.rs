@@ -154,7 +154,7 @@ impl<T, R: RefCount> RefCountedChunkVector<T, R> {
 
         ChunkVector {
             head,
-            chunks: unsafe { std::mem::transmute(self.chunks.into_unique()) },
+            chunks: unsafe { std::mem::transmute::<Vector<RefCountedVector<T, R>>, Vector<SharedVector<T>>>(self.chunks.into_unique()) },
             chunk_size: self.chunk_size,
             len: self.len,
         }
@@ -312,14 +312,14 @@ fn chunks_basic() {
 
         let items: Vec<u32> = v.iter().cloned().collect();
         assert_eq!(items.len(), 80);
-        for i in 0..80 {
-            assert_eq!(items[i], i as u32);
+        for (i, item) in items.iter().enumerate() {
+            assert_eq!(*item, i as u32);
         }
 
         let items: Vec<u32> = v2.iter().cloned().collect();
         assert_eq!(items.len(), 40);
-        for i in 0..40 {
-            assert_eq!(items[i], i as u32);
+        for (i, item) in items.iter().enumerate() {
+            assert_eq!(*item, i as u32);
         }
 
         for i in 0..80 {
@@ -355,8 +355,8 @@ fn unique_chunks() {
 
     let items: Vec<u32> = v.iter().cloned().collect();
     assert_eq!(items.len(), 462);
-    for i in 0..462 {
-        assert_eq!(items[i], i as u32);
+    for (i, item) in items.iter().enumerate() {
+        assert_eq!(*item, i as u32);
     }
 
     let shared = v.into_shared();
@@ -365,8 +365,8 @@ fn unique_chunks() {
 
     let items: Vec<u32> = shared.iter().cloned().collect();
     assert_eq!(items.len(), 462);
-    for i in 0..462 {
-        assert_eq!(items[i], i as u32);
+    for (i, item) in items.iter().enumerate() {
+        assert_eq!(*item, i as u32);
     }
 }
 
@@ -0,0 +1,134 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use shared_vector::Vector;
+
+mod cmd;
+use cmd::*;
+
+// Runs the commands on vectors of zero-sized items and checks their length against a
+// simple model, since zero-sized items can't be told apart.
+fuzz_target!(|cmds: Vec<Cmd>| {
+    let mut vectors: [Vector<()>; 4] = [
+        Vector::new(),
+        Vector::new(),
+        Vector::new(),
+        Vector::new(),
+    ];
+    let mut lengths = [0usize; 4];
+
+    for cmd in cmds {
+        match cmd {
+            Cmd::DropVec { idx } => {
+                vectors[slot(idx)] = Vector::new();
+                lengths[slot(idx)] = 0;
+            }
+            Cmd::Clear { idx } => {
+                vectors[slot(idx)].clear();
+                lengths[slot(idx)] = 0;
+            }
+            Cmd::Push { idx, .. } => {
+                vectors[slot(idx)].push(());
+                lengths[slot(idx)] += 1;
+            }
+            Cmd::PushWithinCapacity { idx, .. } => {
+                if vectors[slot(idx)].push_within_capacity(()).is_ok() {
+                    lengths[slot(idx)] += 1;
+                }
+            }
+            Cmd::Pop { idx } => {
+                if vectors[slot(idx)].pop().is_some() {
+                    lengths[slot(idx)] -= 1;
+                }
+            }
+            Cmd::ExtendFromSlice { idx } => {
+                vectors[slot(idx)].extend_from_slice(&[(), (), ()]);
+                lengths[slot(idx)] += 3;
+            }
+            Cmd::CloneBuffer { src_idx, dst_idx } => {
+                vectors[slot(dst_idx)] = vectors[slot(src_idx)].clone_buffer();
+                lengths[slot(dst_idx)] = lengths[slot(src_idx)];
+            }
+            Cmd::Append { src_idx, dst_idx } => {
+                if slot(src_idx) == slot(dst_idx) {
+                    continue;
+                }
+                let mut v = std::mem::replace(&mut vectors[slot(src_idx)], Vector::new());
+                vectors[slot(dst_idx)].append(&mut v);
+                lengths[slot(dst_idx)] += lengths[slot(src_idx)];
+                lengths[slot(src_idx)] = 0;
+            }
+            Cmd::WithCapacity { idx, cap } => {
+                vectors[slot(idx)] = Vector::with_capacity(cap % 1024);
+                lengths[slot(idx)] = 0;
+            }
+            Cmd::Reserve { idx, val } => {
+                vectors[slot(idx)].reserve(reserve_max(vectors[slot(idx)].len(), val));
+            }
+            Cmd::Convert { idx } => {
+                let a = std::mem::replace(&mut vectors[slot(idx)], Vector::new());
+                vectors[slot(idx)] = a.into_shared().into_unique();
+            }
+            Cmd::Remove { idx, offset } => {
+                let vec = &mut vectors[slot(idx)];
+                if !vec.is_empty() {
+                    vec.remove(offset % vec.len());
+                    lengths[slot(idx)] -= 1;
+                }
+            }
+            Cmd::SwapRemove { idx, offset } => {
+                let vec = &mut vectors[slot(idx)];
+                if !vec.is_empty() {
+                    vec.swap_remove(offset % vec.len());
+                    lengths[slot(idx)] -= 1;
+                }
+            }
+            Cmd::Insert { idx, offset, .. } => {
+                let len = vectors[slot(idx)].len();
+                vectors[slot(idx)].insert(offset % (len + 1), ());
+                lengths[slot(idx)] += 1;
+            }
+            Cmd::ShrinkTo { idx, cap } => {
+                vectors[slot(idx)].shrink_to(cap);
+            }
+            Cmd::ShrinkToFit { idx } => {
+                vectors[slot(idx)].shrink_to_fit();
+            }
+            Cmd::Drain { idx, start, count } => {
+                let vec = &mut vectors[slot(idx)];
+                let len = vec.len();
+                let start = if len > 0 { start % len } else { 0 };
+                let end = (start + (count % 5)).min(len);
+                // Only partially consume the drain to exercise its drop.
+                vec.drain(start..end).next();
+                lengths[slot(idx)] -= end - start;
+            }
+            Cmd::Splice { idx, start, rem_count, add_count, .. } => {
+                let vec = &mut vectors[slot(idx)];
+                let len = vec.len();
+                let start = if len > 0 { start % len } else { 0 };
+                let end = (start + (rem_count % 5)).min(len);
+                let add_count = add_count % 10;
+                vec.splice(start..end, std::iter::repeat_n((), add_count));
+                lengths[slot(idx)] = lengths[slot(idx)] - (end - start) + add_count;
+            }
+            Cmd::Retain { idx, bits } => {
+                let mut i = 0;
+                let mut kept = 0;
+                vectors[slot(idx)].retain(&mut |_: &()| {
+                    i += 1;
+                    let keep = bits & (1 << i.min(31)) != 0;
+                    kept += keep as usize;
+                    keep
+                });
+                lengths[slot(idx)] = kept;
+            }
+            _ => {}
+        }
+
+        for (vec, len) in vectors.iter().zip(lengths.iter()) {
+            assert_eq!(vec.len(), *len);
+            assert!(vec.len() <= vec.capacity());
+        }
+    }
+});
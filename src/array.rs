@@ -0,0 +1,378 @@
+use core::fmt::Debug;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::ptr::{self, NonNull};
+
+use crate::alloc::Global;
+use crate::drain::Drain;
+use crate::raw::{BufferSize, VecHeader};
+use crate::splice::Splice;
+use crate::{RawVector, Vector};
+
+/// A vector that stores up to `N` elements inline and never allocates.
+///
+/// Unlike [`SmallVector`](crate::SmallVector), the items never move to the heap: pushing into a
+/// full vector fails instead. This makes it usable where heap allocation is not available and
+/// in constant expressions:
+///
+/// ```
+/// use shared_vector::ArrayVector;
+/// const EMPTY: ArrayVector<u32, 4> = ArrayVector::new();
+///
+/// let mut v = EMPTY;
+/// v.push(1);
+/// v.push(2);
+/// assert_eq!(v.try_push(3), Ok(()));
+/// assert_eq!(v.capacity(), 4);
+/// assert_eq!(v.as_slice(), &[1, 2, 3]);
+/// ```
+pub struct ArrayVector<T, const N: usize> {
+    // Describes the items so that `drain` and `splice` can share their implementation with `Vector`.
+    // `raw.data` points into `items`, which moves with the vector, so it is refreshed before use.
+    raw: RawVector<T>,
+    items: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArrayVector<T, N> {
+    /// Creates an empty vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` exceeds `u32::MAX`.
+    pub const fn new() -> Self {
+        assert!(N <= BufferSize::MAX as usize);
+        ArrayVector {
+            raw: RawVector {
+                data: NonNull::dangling(),
+                header: VecHeader { len: 0, cap: N as BufferSize },
+            },
+            items: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+        }
+    }
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.header.len as usize
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total number of elements the vector can hold, which is `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns number of elements that can be added before the vector is full.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Returns `true` if the vector can't hold any more elements.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.items.as_ptr() as *const T, self.len()) }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len()) }
+    }
+
+    // Points `raw` at the items, which may have moved since the last call.
+    #[inline]
+    fn raw_mut(&mut self) -> &mut RawVector<T> {
+        unsafe {
+            self.raw.data = NonNull::new_unchecked(self.items.as_mut_ptr() as *mut T);
+        }
+        &mut self.raw
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is full.
+    #[inline]
+    pub fn push(&mut self, val: T) {
+        if self.try_push(val).is_err() {
+            panic!("push exceeds the capacity of the vector (is {N})");
+        }
+    }
+
+    /// Appends an element to the back of the vector, or returns it if the vector is full.
+    #[inline]
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
+        let len = self.len();
+        if len == N {
+            return Err(val);
+        }
+
+        self.items[len].write(val);
+        self.raw.header.len += 1;
+
+        Ok(())
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        self.raw.header.len -= 1;
+        unsafe { Some(self.items[len - 1].assume_init_read()) }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+    ///
+    /// If `len` is greater or equal to the vector’s current length, this has no effect.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.raw_mut().truncate(len)
+    }
+
+    /// Clears the vector, removing all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator. If the iterator is dropped before
+    /// being fully consumed, it drops the remaining removed elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.raw_mut().drain(range)
+    }
+
+    /// Creates a splicing iterator that replaces the specified range in the vector
+    /// with the given `replace_with` iterator and yields the removed items.
+    ///
+    /// The replacement happens when the `Splice` is dropped, see [`Vector::splice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    /// Dropping the `Splice` panics if the items don't fit in the vector.
+    pub fn splice<R, I>(
+        &mut self,
+        range: R,
+        replace_with: I
+    ) -> Splice<'_, <I as IntoIterator>::IntoIter, Global>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        // Derive both the drain's pointers and the splice's pointer from the same raw pointer.
+        let vec: *mut RawVector<T> = self.raw_mut();
+        unsafe {
+            Splice {
+                drain: Drain::new((*vec).data, NonNull::new_unchecked(ptr::addr_of_mut!((*vec).header)), range),
+                vec: NonNull::new_unchecked(vec),
+                replace_with: replace_with.into_iter(),
+                allocator: None,
+            }
+        }
+    }
+
+    /// Moves the items into a heap allocated `Vector`, with a capacity equal to the length.
+    pub fn into_vector(mut self) -> Vector<T, Global> {
+        let len = self.len();
+        let mut vector = Vector::with_capacity(len);
+        unsafe {
+            ptr::copy_nonoverlapping(self.items.as_ptr() as *const T, vector.as_mut_ptr(), len);
+            self.raw.header.len = 0;
+            vector.set_len(len);
+        }
+
+        vector
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayVector<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+impl<T, const N: usize> Default for ArrayVector<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVector<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        for item in self.as_slice() {
+            clone.push(item.clone());
+        }
+        clone
+    }
+}
+
+impl<T: PartialEq<T>, const N: usize> PartialEq<ArrayVector<T, N>> for ArrayVector<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, const N: usize> PartialEq<&[T]> for ArrayVector<T, N> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVector<T, N> {}
+
+impl<T, const N: usize> AsRef<[T]> for ArrayVector<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for ArrayVector<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayVector<T, N> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVector<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayVector<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayVector<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> core::slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayVector<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+    fn into_iter(self) -> core::slice::IterMut<'a, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+/// Panics if the iterator yields more items than the vector can hold.
+impl<T, const N: usize> Extend<T> for ArrayVector<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// Panics if the iterator yields more than `N` items.
+impl<T, const N: usize> FromIterator<T> for ArrayVector<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = ArrayVector::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+impl<T, const N: usize> From<ArrayVector<T, N>> for Vector<T, Global> {
+    fn from(vector: ArrayVector<T, N>) -> Self {
+        vector.into_vector()
+    }
+}
+
+unsafe impl<T: Send, const N: usize> Send for ArrayVector<T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for ArrayVector<T, N> {}
+
+// Only uses `core`, so that it also covers builds without `std` or `alloc`.
+#[test]
+fn array_no_alloc() {
+    static mut V: ArrayVector<u32, 4> = ArrayVector::new();
+
+    let mut v = unsafe { core::mem::take(&mut *ptr::addr_of_mut!(V)) };
+    for i in 0..4 {
+        v.push(i);
+    }
+    assert!(v.is_full());
+    assert_eq!(v.try_push(4), Err(4));
+    assert_eq!(v.pop(), Some(3));
+    v.truncate(2);
+    assert_eq!(v.as_slice(), &[0, 1]);
+
+    let mut drained = v.drain(..1);
+    assert_eq!(drained.next(), Some(0));
+    assert_eq!(drained.next(), None);
+    drop(drained);
+
+    v.splice(0..0, [5, 6]).for_each(drop);
+    assert_eq!(v.as_slice(), &[5, 6, 1]);
+    v.splice(3.., core::iter::once(7)).for_each(drop);
+    assert_eq!(v.as_slice(), &[5, 6, 1, 7]);
+    v.clear();
+    assert!(v.is_empty());
+}
+
+#[test]
+fn array_drain_splice() {
+    let mut v: ArrayVector<Box<u32>, 8> = (0..6).map(Box::new).collect();
+    let drained: Vec<Box<u32>> = v.drain(1..3).collect();
+    assert_eq!(drained, vec![Box::new(1), Box::new(2)]);
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(3), Box::new(4), Box::new(5)][..]);
+
+    // Move the vector to make sure drain and splice don't use stale pointers.
+    let mut v = Box::new(v);
+    let removed: Vec<Box<u32>> = v.splice(1..2, (10..13).map(Box::new)).collect();
+    assert_eq!(removed, vec![Box::new(3)]);
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(10), Box::new(11), Box::new(12), Box::new(4), Box::new(5)][..]);
+
+    let c = v.clone();
+    let vector: Vector<Box<u32>> = (*v).into();
+    assert_eq!(vector.as_slice(), c.as_slice());
+    assert_eq!(vector.capacity(), 6);
+}
+
+#[test]
+#[should_panic]
+fn array_splice_overflow() {
+    let mut v: ArrayVector<Box<u32>, 4> = (0..3).map(Box::new).collect();
+    v.splice(1..2, (10..13).map(Box::new)).for_each(drop);
+}
@@ -3,23 +3,33 @@
 use core::fmt;
 use core::iter::FusedIterator;
 use core::mem;
+use core::ops::{Range, RangeBounds};
 use core::ptr::{self, NonNull};
 use core::slice;
 
-use super::RawVector;
+use crate::alloc::Allocator;
+use crate::raw::{BufferSize, VecHeader};
+use crate::{GrowthStrategy, Vector};
+use crate::vector::range_bounds;
 
 /// A draining iterator for `Vector<T>`.
 ///
-/// This `struct` is created by [`Vector::drain`].
-/// See its documentation for more.
+/// This `struct` is created by [`Vector::drain`](crate::Vector::drain) and
+/// [`RefCountedVector::drain`](crate::RefCountedVector::drain).
+/// See their documentation for more.
 pub struct Drain<'a, T: 'a> {
     /// Index of tail to preserve
     pub(super) tail_start: usize,
     /// Length of tail
     pub(super) tail_len: usize,
+    /// Length of the range that was initially drained
+    pub(super) drain_len: usize,
     /// Current remaining range to remove
     pub(super) iter: slice::Iter<'a, T>,
-    pub(super) vec: NonNull<RawVector<T>>,
+    /// The items of the source vector
+    pub(super) data: NonNull<T>,
+    /// The header of the source vector, which holds its length
+    pub(super) header: NonNull<VecHeader>,
 }
 
 impl<T: fmt::Debug> fmt::Debug for Drain<'_, T> {
@@ -29,6 +39,47 @@ impl<T: fmt::Debug> fmt::Debug for Drain<'_, T> {
 }
 
 impl<'a, T> Drain<'a, T> {
+    /// # Safety
+    ///
+    /// `data` and `header` must belong to the same vector, which must not be accessed
+    /// through other pointers for the lifetime of the drain.
+    pub(crate) unsafe fn new<R>(data: NonNull<T>, mut header: NonNull<VecHeader>, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        // Memory safety
+        //
+        // When the Drain is first created, it shortens the length of
+        // the source vector to make sure no uninitialized or moved-from elements
+        // are accessible at all if the Drain's destructor never gets to run.
+        //
+        // Drain will ptr::read out the values to remove.
+        // When finished, remaining tail of the vec is copied back to cover
+        // the hole, and the vector length is restored to the new length.
+        //
+        let len = header.as_ref().len as usize;
+        let (start, end) = range_bounds(&range, len);
+
+        unsafe {
+            // Set self.vec length's to start, to be safe in case Drain is leaked
+            header.as_mut().len = start as BufferSize;
+            let range_slice = slice::from_raw_parts(data.as_ptr().add(start), end - start);
+            Drain {
+                tail_start: end,
+                tail_len: len - end,
+                drain_len: end - start,
+                iter: range_slice.iter(),
+                data,
+                header,
+            }
+        }
+    }
+
+    #[inline]
+    fn vec_len(&self) -> usize {
+        unsafe { self.header.as_ref().len as usize }
+    }
+
     /// Returns the remaining items of this iterator as a slice.
     ///
     /// # Examples
@@ -44,6 +95,163 @@ impl<'a, T> Drain<'a, T> {
     pub fn as_slice(&self) -> &[T] {
         self.iter.as_slice()
     }
+
+    /// Returns a reference to the next item without consuming it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut vec = vector!['a', 'b', 'c'];
+    /// let mut drain = vec.drain(..);
+    /// assert_eq!(drain.peek(), Some(&'a'));
+    /// assert_eq!(drain.next(), Some('a'));
+    /// assert_eq!(drain.peek(), Some(&'b'));
+    /// ```
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.iter.as_slice().first()
+    }
+
+    /// Returns a reference to the next item from the back without consuming it.
+    #[must_use]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.iter.as_slice().last()
+    }
+
+    /// Returns the range of indices that is being drained from the original vector.
+    ///
+    /// The range does not change as items are consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut vec = vector!['a', 'b', 'c', 'd'];
+    /// let mut drain = vec.drain(1..3);
+    /// let _ = drain.next().unwrap();
+    /// assert_eq!(drain.range(), 1..3);
+    /// ```
+    pub fn range(&self) -> Range<usize> {
+        let start = self.vec_len();
+        start..(start + self.drain_len)
+    }
+
+    /// Returns the remaining items of this iterator as a mutable slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut vec = vector![1.0f32, 2.0, 3.0];
+    /// let mut drain = vec.drain(..);
+    /// let _ = drain.next().unwrap();
+    /// for val in drain.as_mut_slice() {
+    ///     *val *= 2.0;
+    /// }
+    /// assert_eq!(drain.as_slice(), &[4.0, 6.0]);
+    /// ```
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.iter.len();
+        if len == 0 {
+            return &mut [];
+        }
+
+        unsafe {
+            // Like in `Drop`, the pointer must be reconstructed from the original vec to
+            // get mutable provenance, since the slice iterator only gives us a `&[T]`.
+            let vec_ptr = self.data.as_ptr();
+            let offset = if mem::size_of::<T>() == 0 {
+                0
+            } else {
+                sub_ptr(self.iter.as_slice().as_ptr(), vec_ptr)
+            };
+
+            slice::from_raw_parts_mut(vec_ptr.add(offset), len)
+        }
+    }
+
+    /// Keep unyielded elements in the source vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut vec = vector!['a', 'b', 'c'];
+    /// let mut drain = vec.drain(..);
+    ///
+    /// assert_eq!(drain.next().unwrap(), 'a');
+    ///
+    /// // This call keeps 'b' and 'c' in the vector.
+    /// drain.keep_rest();
+    ///
+    /// // If we wouldn't call `keep_rest()`,
+    /// // `vec` would be empty.
+    /// assert_eq!(vec.as_slice(), &['b', 'c']);
+    /// ```
+    pub fn keep_rest(mut self) {
+        let unyielded_len = self.iter.len();
+        if unyielded_len == 0 {
+            return;
+        }
+
+        // Turn the unyielded elements into part of the tail so that `Drop` moves them back
+        // alongside it instead of dropping them.
+        let unyielded_start = if mem::size_of::<T>() == 0 {
+            self.vec_len()
+        } else {
+            let unyielded_ptr = self.iter.as_slice().as_ptr();
+            sub_ptr(unyielded_ptr, self.data.as_ptr())
+        };
+
+        if unyielded_start + unyielded_len != self.tail_start {
+            // Some elements were yielded from the back, close the gap between the
+            // unyielded elements and the tail.
+            unsafe {
+                let vec_ptr = self.data.as_ptr();
+                let src = vec_ptr.add(self.tail_start);
+                let dst = vec_ptr.add(unyielded_start + unyielded_len);
+                ptr::copy(src, dst, self.tail_len);
+            }
+        }
+
+        self.tail_start = unyielded_start;
+        self.tail_len += unyielded_len;
+        self.iter = [].iter();
+    }
+
+    /// Moves the remaining items to the back of `dst` and completes the drain.
+    ///
+    /// Unlike `dst.extend(drain)`, the items are moved in a single bulk copy after
+    /// reserving space in `dst` once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut src = vector![1, 2, 3, 4];
+    /// let mut dst = vector![0];
+    /// src.drain(1..3).collect_into_existing(&mut dst);
+    /// assert_eq!(src.as_slice(), &[1, 4]);
+    /// assert_eq!(dst.as_slice(), &[0, 2, 3]);
+    /// ```
+    pub fn collect_into_existing<A: Allocator, G: GrowthStrategy>(mut self, dst: &mut Vector<T, A, G>) {
+        let count = self.iter.len();
+        if count == 0 {
+            return;
+        }
+
+        dst.reserve(count);
+        unsafe {
+            let len = dst.len();
+            ptr::copy_nonoverlapping(self.iter.as_slice().as_ptr(), dst.as_mut_ptr().add(len), count);
+            dst.set_len(len + count);
+        }
+
+        // The items now belong to `dst`, don't let `Drop` drop them.
+        self.iter = [].iter();
+    }
 }
 
 impl<'a, T> AsRef<[T]> for Drain<'a, T> {
@@ -68,6 +276,12 @@ impl<T> Iterator for Drain<'_, T> {
     }
 }
 
+impl<T> ExactSizeIterator for Drain<'_, T> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
 impl<T> DoubleEndedIterator for Drain<'_, T> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
@@ -84,37 +298,36 @@ impl<T> Drop for Drain<'_, T> {
             fn drop(&mut self) {
                 if self.0.tail_len > 0 {
                     unsafe {
-                        let source_vec = self.0.vec.as_mut();
+                        let vec_ptr = self.0.data.as_ptr();
                         // memmove back untouched tail, update to new length
-                        let start = source_vec.len();
+                        let start = self.0.vec_len();
                         let tail = self.0.tail_start;
                         if tail != start {
-                            let src = source_vec.as_ptr().add(tail);
-                            let dst = source_vec.as_mut_ptr().add(start);
+                            let src = vec_ptr.add(tail);
+                            let dst = vec_ptr.add(start);
                             ptr::copy(src, dst, self.0.tail_len);
                         }
-                        source_vec.header.len = (start + self.0.tail_len) as u32;
+                        self.0.header.as_mut().len = (start + self.0.tail_len) as BufferSize;
                     }
                 }
             }
         }
 
-        let iter = mem::replace(&mut self.iter, (&mut []).iter());
+        let iter = mem::replace(&mut self.iter, [].iter());
         let drop_len = iter.len();
 
-        let mut vec = self.vec;
+        let vec_ptr = self.data.as_ptr();
 
-        // if T::IS_ZST {
-        //     // ZSTs have no identity, so we don't need to move them around, we only need to drop the correct amount.
-        //     // this can be achieved by manipulating the Vec length instead of moving values out from `iter`.
-        //     unsafe {
-        //         let vec = vec.as_mut();
-        //         let old_len = vec.len();
-        //         vec.set_len(old_len + drop_len + self.tail_len);
-        //         vec.truncate(old_len + self.tail_len);
-        //     }
-        //     return;
-        // }
+        if mem::size_of::<T>() == 0 {
+            // ZSTs have no identity, so we don't need to move them around, we only need to drop the correct amount.
+            // this can be achieved by manipulating the Vec length instead of moving values out from `iter`.
+            unsafe {
+                let old_len = self.vec_len();
+                self.header.as_mut().len = (old_len + self.tail_len) as BufferSize;
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(vec_ptr, drop_len));
+            }
+            return;
+        }
 
         // ensure elements are moved back into their appropriate places, even when drop_in_place panics
         let _guard = DropGuard(self);
@@ -134,7 +347,6 @@ impl<T> Drop for Drain<'_, T> {
             // a pointer with mutable provenance is necessary. Therefore we must reconstruct
             // it from the original vec but also avoid creating a &mut to the front since that could
             // invalidate raw pointers to it which some unsafe code might rely on.
-            let vec_ptr = vec.as_mut().as_mut_ptr();
             let drop_offset = sub_ptr(drop_ptr, vec_ptr);
             let to_drop = ptr::slice_from_raw_parts_mut(vec_ptr.add(drop_offset), drop_len);
             ptr::drop_in_place(to_drop);
@@ -150,3 +362,112 @@ fn sub_ptr<T>(a: *const T, b: *const T) -> usize {
 
 impl<T> FusedIterator for Drain<'_, T> {}
 
+#[cfg(feature = "nightly")]
+unsafe impl<T> core::iter::TrustedLen for Drain<'_, T> {}
+
+
+#[test]
+fn drain_exact_size() {
+    let mut vec = crate::vector![1u32, 2, 3, 4, 5];
+    let mut drain = vec.drain(1..3);
+    assert_eq!(drain.len(), 2);
+    drain.next();
+    assert_eq!(drain.len(), 1);
+    drain.next();
+    assert_eq!(drain.len(), 0);
+}
+
+#[test]
+fn drain_keep_rest() {
+    let mut vec = crate::vector![Box::new(1u32), Box::new(2), Box::new(3), Box::new(4), Box::new(5), Box::new(6)];
+    let mut drain = vec.drain(1..5);
+    assert_eq!(drain.next(), Some(Box::new(2)));
+    assert_eq!(drain.next_back(), Some(Box::new(5)));
+    drain.keep_rest();
+    assert_eq!(vec.as_slice(), &[Box::new(1), Box::new(3), Box::new(4), Box::new(6)]);
+
+    let mut drain = vec.drain(..2);
+    drain.by_ref().for_each(drop);
+    drain.keep_rest();
+    assert_eq!(vec.as_slice(), &[Box::new(4), Box::new(6)]);
+
+    let mut zst = crate::vector![(), (), (), ()];
+    let mut drain = zst.drain(1..3);
+    drain.next();
+    drain.keep_rest();
+    assert_eq!(zst.len(), 3);
+}
+
+#[test]
+fn drain_as_mut_slice() {
+    let mut vec = crate::vector![1u32, 2, 3, 4, 5];
+    let mut drain = vec.drain(1..4);
+    assert_eq!(drain.next(), Some(2));
+    for val in drain.as_mut_slice() {
+        *val *= 10;
+    }
+    assert_eq!(drain.as_slice(), &[30, 40]);
+    assert_eq!(drain.collect::<Vec<_>>(), vec![30, 40]);
+    assert_eq!(vec.as_slice(), &[1, 5]);
+}
+
+#[test]
+fn drain_range() {
+    let mut vec = crate::vector![1u32, 2, 3, 4, 5];
+    let mut drain = vec.drain(1..4);
+    assert_eq!(drain.range(), 1..4);
+    drain.next();
+    drain.next_back();
+    assert_eq!(drain.range(), 1..4);
+    drop(drain);
+
+    let drain = vec.drain(..);
+    assert_eq!(drain.range(), 0..2);
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn drain_trusted_len() {
+    let mut vec = crate::vector![1u32, 2, 3, 4, 5];
+    let collected: Vec<u32> = vec.drain(1..4).collect();
+    assert_eq!(collected, vec![2, 3, 4]);
+    assert_eq!(collected.capacity(), 3);
+}
+
+#[test]
+fn collect_into_existing() {
+    let mut src = crate::vector![Box::new(1u32), Box::new(2), Box::new(3), Box::new(4), Box::new(5)];
+    let mut dst = crate::vector![Box::new(0u32)];
+
+    let mut drain = src.drain(1..4);
+    assert_eq!(drain.next_back(), Some(Box::new(4)));
+    drain.collect_into_existing(&mut dst);
+    assert_eq!(src.as_slice(), &[Box::new(1), Box::new(5)]);
+    assert_eq!(dst.as_slice(), &[Box::new(0), Box::new(2), Box::new(3)]);
+
+    src.drain(..0).collect_into_existing(&mut dst);
+    assert_eq!(src.len(), 2);
+    assert_eq!(dst.len(), 3);
+
+    src.drain(..).collect_into_existing(&mut dst);
+    assert!(src.is_empty());
+    assert_eq!(dst.as_slice(), &[Box::new(0), Box::new(2), Box::new(3), Box::new(1), Box::new(5)]);
+}
+
+#[test]
+fn peek() {
+    let mut vec = crate::vector![1u32, 2, 3, 4];
+    let mut drain = vec.drain(1..);
+    let first = drain.peek().unwrap() as *const u32;
+    assert_eq!(drain.peek().unwrap() as *const u32, first);
+    assert_eq!(drain.peek(), Some(&2));
+    assert_eq!(drain.peek_back(), Some(&4));
+    assert_eq!(drain.len(), 3);
+
+    assert_eq!(drain.next_back(), Some(4));
+    assert_eq!(drain.peek_back(), Some(&3));
+    assert_eq!(drain.next(), Some(2));
+    assert_eq!(drain.next(), Some(3));
+    assert_eq!(drain.peek(), None);
+    assert_eq!(drain.peek_back(), None);
+}
@@ -0,0 +1,137 @@
+// Most of the code in this file is copied from std::Vec's ExtractIf implementation.
+
+use core::fmt;
+use core::ptr;
+use core::slice;
+
+use super::RawVector;
+
+/// An iterator which uses a closure to determine if an element should be removed.
+///
+/// This `struct` is created by [`Vector::extract_if`](crate::Vector::extract_if).
+/// See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    pub(super) vec: &'a mut RawVector<T>,
+    /// The index of the item that will be inspected by the next call to `next`.
+    pub(super) idx: usize,
+    /// Elements at and beyond this point will be retained. Must be equal or smaller than `old_len`.
+    pub(super) end: usize,
+    /// The number of items that have been drained (removed) thus far.
+    pub(super) del: usize,
+    /// The original length of `vec` prior to draining.
+    pub(super) old_len: usize,
+    /// The filter test predicate.
+    pub(super) pred: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.end {
+                let i = self.idx;
+                let v = slice::from_raw_parts_mut(self.vec.data_ptr(), self.old_len);
+                let drained = (self.pred)(&mut v[i]);
+                // Update the index *after* the predicate is called. If the index
+                // is updated prior and the predicate panics, the element at this
+                // index would be leaked.
+                self.idx += 1;
+                if drained {
+                    self.del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.del > 0 {
+                    let del = self.del;
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i - del];
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<T, F> Drop for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let ptr = self.vec.data_ptr();
+                let src = ptr.add(self.idx);
+                let dst = src.sub(self.del);
+                let tail_len = self.old_len - self.idx;
+                src.copy_to(dst, tail_len);
+            }
+            self.vec.set_len(self.old_len - self.del);
+        }
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let peek = if self.idx < self.end {
+            unsafe { Some(&*self.vec.data_ptr().add(self.idx)) }
+        } else {
+            None
+        };
+        f.debug_struct("ExtractIf").field("peek", &peek).finish_non_exhaustive()
+    }
+}
+
+#[test]
+fn extract_if() {
+    let mut v: crate::Vector<u32> = (0..10).collect();
+    let evens: Vec<u32> = v.extract_if(.., |x| *x % 2 == 0).collect();
+    assert_eq!(evens, vec![0, 2, 4, 6, 8]);
+    assert_eq!(v.as_slice(), &[1, 3, 5, 7, 9]);
+
+    // Only the items in the range are inspected.
+    let mut v: crate::Vector<u32> = (0..10).collect();
+    let extracted: Vec<u32> = v.extract_if(2..6, |_| true).collect();
+    assert_eq!(extracted, vec![2, 3, 4, 5]);
+    assert_eq!(v.as_slice(), &[0, 1, 6, 7, 8, 9]);
+
+    // Items the iterator did not reach are kept when it is dropped early.
+    let mut v: crate::Vector<Box<u32>> = (0..6).map(Box::new).collect();
+    let mut iter = v.extract_if(.., |x| **x % 2 == 1);
+    assert_eq!(iter.next(), Some(Box::new(1)));
+    drop(iter);
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(2), Box::new(3), Box::new(4), Box::new(5)]);
+
+    // The predicate can mutate the items it keeps.
+    let mut v: crate::Vector<u32> = (0..4).collect();
+    v.extract_if(.., |x| { *x *= 10; false }).for_each(drop);
+    assert_eq!(v.as_slice(), &[0, 10, 20, 30]);
+}
+
+#[test]
+fn extract_if_panic() {
+    let mut v: crate::Vector<Box<u32>> = (0..6).map(Box::new).collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.extract_if(.., |x| {
+            assert!(**x != 3);
+            **x % 2 == 0
+        })
+        .for_each(drop);
+    }));
+    assert!(result.is_err());
+    // The items that were not extracted before the panic are kept, nothing is leaked or dropped twice.
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(3), Box::new(4), Box::new(5)]);
+}
@@ -0,0 +1,282 @@
+use core::fmt::Debug;
+use core::mem;
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::ptr::{self, NonNull};
+
+use crate::alloc::Global;
+use crate::drain::Drain;
+use crate::raw::{BufferSize, VecHeader};
+use crate::splice::Splice;
+use crate::RawVector;
+
+/// A vector with a fixed capacity.
+///
+/// The buffer is allocated once with room for exactly `capacity` items and never grows.
+/// Pushing into a full vector returns the item back instead of reallocating.
+pub struct FixedVector<T> {
+    raw: RawVector<T>,
+}
+
+impl<T> FixedVector<T> {
+    /// Creates an empty vector with room for `cap` items.
+    ///
+    /// Does not allocate memory if `cap` is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocation fails or if `cap` exceeds `u32::MAX`.
+    pub fn from_capacity(cap: usize) -> Self {
+        let mut raw = RawVector::try_with_capacity(&Global, cap).unwrap();
+        // Zero-sized items don't need storage so the raw vector reports a capacity of `u32::MAX`,
+        // the vector is still only allowed to hold `cap` of them.
+        if mem::size_of::<T>() == 0 {
+            raw.header.cap = cap as BufferSize;
+        }
+        FixedVector { raw }
+    }
+
+    /// Creates a vector from its raw parts.
+    ///
+    /// # Safety
+    ///
+    /// `ptr`, `len` and `cap` must come from a previous call to [`into_raw_parts`](Self::into_raw_parts)
+    /// or [`Vector::into_raw_parts`](crate::Vector::into_raw_parts).
+    /// The buffer is not just a slice of items: it is preceded by space that this crate reserves
+    /// for its header, so pointers obtained from other containers cannot be used.
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize, cap: usize) -> Self {
+        debug_assert!(len <= cap);
+        FixedVector {
+            raw: RawVector {
+                data: NonNull::new_unchecked(ptr),
+                header: VecHeader { len: len as BufferSize, cap: cap as BufferSize },
+            },
+        }
+    }
+
+    /// Decomposes the vector into a pointer to its first item, its length and its capacity.
+    ///
+    /// The caller becomes responsible for the memory, which can be released by turning it
+    /// back into a vector with [`from_raw_parts`](Self::from_raw_parts).
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let this = mem::ManuallyDrop::new(self);
+        (this.raw.data_ptr(), this.raw.len(), this.raw.capacity())
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns `true` if the vector can't hold any more elements.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.raw.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns the total number of elements the vector can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.raw.capacity()
+    }
+
+    /// Returns number of elements that can be added before the vector is full.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.raw.remaining_capacity()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.raw.as_slice()
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.raw.as_mut_slice()
+    }
+
+    /// Appends an element to the back of the vector, or returns it if the vector is full.
+    #[inline]
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
+        self.raw.push_within_capacity(val)
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw.pop()
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+    ///
+    /// If `len` is greater or equal to the vector’s current length, this has no effect.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.raw.truncate(len)
+    }
+
+    /// Clears the vector, removing all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.raw.clear()
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator. If the iterator is dropped before
+    /// being fully consumed, it drops the remaining removed elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.raw.drain(range)
+    }
+
+    /// Creates a splicing iterator that replaces the specified range in the vector
+    /// with the given `replace_with` iterator and yields the removed items.
+    ///
+    /// The replacement happens when the `Splice` is dropped, see [`Vector::splice`](crate::Vector::splice).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    /// Dropping the `Splice` panics if the items don't fit in the vector.
+    pub fn splice<R, I>(
+        &mut self,
+        range: R,
+        replace_with: I
+    ) -> Splice<'_, <I as IntoIterator>::IntoIter, Global>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        let vec: *mut RawVector<T> = &mut self.raw;
+        unsafe {
+            Splice {
+                drain: Drain::new((*vec).data, NonNull::new_unchecked(ptr::addr_of_mut!((*vec).header)), range),
+                vec: NonNull::new_unchecked(vec),
+                replace_with: replace_with.into_iter(),
+                allocator: None,
+            }
+        }
+    }
+}
+
+impl<T> Drop for FixedVector<T> {
+    fn drop(&mut self) {
+        unsafe { self.raw.deallocate(&Global) }
+    }
+}
+
+impl<T> Deref for FixedVector<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for FixedVector<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Debug> Debug for FixedVector<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for FixedVector<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+unsafe impl<T: Send> Send for FixedVector<T> {}
+unsafe impl<T: Sync> Sync for FixedVector<T> {}
+
+#[test]
+fn fixed_push_until_full() {
+    let mut v = FixedVector::from_capacity(3);
+    assert_eq!(v.capacity(), 3);
+    assert!(v.try_push(Box::new(1u32)).is_ok());
+    assert!(v.try_push(Box::new(2)).is_ok());
+    assert!(v.try_push(Box::new(3)).is_ok());
+    assert!(v.is_full());
+    assert_eq!(v.try_push(Box::new(4)), Err(Box::new(4)));
+    assert_eq!(v.capacity(), 3);
+
+    assert_eq!(v.pop(), Some(Box::new(3)));
+    assert_eq!(v.remaining_capacity(), 1);
+    assert!(v.try_push(Box::new(5)).is_ok());
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(5)]);
+
+    let drained: Vec<Box<u32>> = v.drain(..2).collect();
+    assert_eq!(drained, vec![Box::new(1), Box::new(2)]);
+    assert_eq!(v.as_slice(), &[Box::new(5)]);
+    assert_eq!(v.capacity(), 3);
+
+    let mut empty: FixedVector<u32> = FixedVector::from_capacity(0);
+    assert_eq!(empty.try_push(1), Err(1));
+}
+
+#[test]
+fn fixed_zero_sized() {
+    let mut v: FixedVector<()> = FixedVector::from_capacity(3);
+    assert_eq!(v.capacity(), 3);
+    for _ in 0..3 {
+        v.try_push(()).unwrap();
+    }
+    assert!(v.is_full());
+    assert_eq!(v.try_push(()), Err(()));
+    assert_eq!(v.len(), 3);
+}
+
+#[test]
+fn fixed_splice() {
+    let mut v = FixedVector::from_capacity(5);
+    for i in 0..4 {
+        v.try_push(Box::new(i)).unwrap();
+    }
+    let removed: Vec<Box<u32>> = v.splice(1..3, [Box::new(10), Box::new(11), Box::new(12)]).collect();
+    assert_eq!(removed, vec![Box::new(1), Box::new(2)]);
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(10), Box::new(11), Box::new(12), Box::new(3)]);
+    assert_eq!(v.capacity(), 5);
+}
+
+#[test]
+#[should_panic]
+fn fixed_splice_overflow() {
+    let mut v = FixedVector::from_capacity(4);
+    for i in 0..3 {
+        v.try_push(Box::new(i)).unwrap();
+    }
+    v.splice(1..2, (10..13).map(Box::new)).for_each(drop);
+}
+
+#[test]
+fn fixed_raw_parts() {
+    let mut v = FixedVector::from_capacity(4);
+    v.try_push(Box::new(1u32)).unwrap();
+    v.try_push(Box::new(2)).unwrap();
+
+    let (ptr, len, cap) = v.into_raw_parts();
+    assert_eq!((len, cap), (2, 4));
+
+    let mut v = unsafe { FixedVector::from_raw_parts(ptr, len, cap) };
+    v.try_push(Box::new(3)).unwrap();
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+}
@@ -0,0 +1,92 @@
+use crate::BufferSize;
+
+/// Decides how much the capacity of a [`Vector`](crate::Vector) grows when it runs out of space.
+///
+/// The strategy is a type parameter of `Vector`, which defaults to [`DoublingGrowth`].
+/// It applies to the methods that append or insert items and to `reserve`. Splicing still
+/// grows the buffer with the default strategy.
+pub trait GrowthStrategy {
+    /// Returns the new capacity of a vector of length `len` that needs room for `required` items.
+    ///
+    /// The returned capacity must be at least `required`.
+    fn next_capacity(len: usize, required: usize) -> usize;
+}
+
+/// Doubles the capacity, with a minimum of 8 items.
+///
+/// This is the default strategy. It minimizes the number of reallocations at the cost of
+/// leaving up to half of the buffer unused.
+pub struct DoublingGrowth;
+
+/// Grows the capacity by a factor of 1.625, close to the golden ratio, with a minimum of 8 items.
+///
+/// This trades more frequent reallocations for less unused memory than [`DoublingGrowth`].
+pub struct GoldenRatioGrowth;
+
+/// Grows the capacity to exactly the number of required items.
+///
+/// This never over-allocates, but pushing items one at a time reallocates each time.
+pub struct ExactGrowth;
+
+impl GrowthStrategy for DoublingGrowth {
+    #[inline]
+    fn next_capacity(len: usize, required: usize) -> usize {
+        crate::grow_amortized(len, required - len)
+    }
+}
+
+impl GrowthStrategy for GoldenRatioGrowth {
+    #[inline]
+    fn next_capacity(len: usize, required: usize) -> usize {
+        let cap = len.saturating_add(len / 2 + len / 8).max(required).max(8);
+
+        const MAX: usize = BufferSize::MAX as usize;
+        if cap > MAX && required <= MAX {
+            return MAX;
+        }
+
+        cap
+    }
+}
+
+impl GrowthStrategy for ExactGrowth {
+    #[inline]
+    fn next_capacity(_len: usize, required: usize) -> usize {
+        required
+    }
+}
+
+#[test]
+fn growth_strategies() {
+    use crate::Vector;
+    use crate::alloc::Global;
+
+    assert_eq!(DoublingGrowth::next_capacity(0, 1), 8);
+    assert_eq!(DoublingGrowth::next_capacity(16, 17), 32);
+    assert_eq!(GoldenRatioGrowth::next_capacity(0, 1), 8);
+    assert_eq!(GoldenRatioGrowth::next_capacity(16, 17), 26);
+    assert_eq!(GoldenRatioGrowth::next_capacity(16, 40), 40);
+    assert_eq!(ExactGrowth::next_capacity(16, 17), 17);
+
+    let mut v: Vector<u32, Global, ExactGrowth> = Vector::new_in(Global);
+    for i in 0..10 {
+        v.push(i);
+        assert_eq!(v.capacity(), v.len());
+    }
+    v.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(v.capacity(), 13);
+
+    let mut v: Vector<u32, Global, GoldenRatioGrowth> = Vector::new_in(Global);
+    for i in 0..100 {
+        v.push(i);
+    }
+    for i in 0..100 {
+        v.insert(0, i);
+    }
+    assert_eq!(v.len(), 200);
+    assert!(v.capacity() < 400);
+
+    let mut v: Vector<u32> = Vector::new();
+    v.push(1);
+    assert!(v.capacity() >= 8);
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ptr;
+
+use crate::alloc::{Allocator, Global};
+use crate::vector::RawVector;
+
+/// An iterator that moves out of a vector.
+///
+/// This `struct` is created by the `into_iter` method on [`Vector`](crate::Vector)
+/// (provided by the [`IntoIterator`] trait).
+pub struct IntoIter<T, A: Allocator = Global> {
+    // The length of the raw vector is set to zero, the remaining items are tracked
+    // by `start` and `end`.
+    pub(crate) raw: RawVector<T>,
+    pub(crate) allocator: A,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl<T, A: Allocator> IntoIter<T, A> {
+    /// Returns the remaining items of this iterator as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.raw.data.as_ptr().add(self.start), self.len()) }
+    }
+
+    /// Returns the remaining items of this iterator as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.raw.data.as_ptr().add(self.start), self.len()) }
+    }
+
+    /// Returns a reference to the underlying allocator.
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, A: Allocator> AsRef<[T]> for IntoIter<T, A> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        let idx = self.start;
+        self.start += 1;
+
+        unsafe { Some(ptr::read(self.raw.data.as_ptr().add(idx))) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+
+        unsafe { Some(ptr::read(self.raw.data.as_ptr().add(self.end))) }
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        // Deallocates the buffer even if dropping one of the remaining items panics.
+        struct DropGuard<'a, T, A: Allocator>(&'a mut IntoIter<T, A>);
+
+        impl<T, A: Allocator> Drop for DropGuard<'_, T, A> {
+            fn drop(&mut self) {
+                unsafe {
+                    self.0.raw.deallocate(&self.0.allocator);
+                }
+            }
+        }
+
+        let guard = DropGuard(self);
+        unsafe {
+            ptr::drop_in_place(guard.0.as_mut_slice());
+        }
+    }
+}
+
+#[test]
+fn into_iter() {
+    let v = crate::vector![Box::new(1u32), Box::new(2), Box::new(3), Box::new(4)];
+    let mut iter = v.into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(Box::new(1)));
+    assert_eq!(iter.next_back(), Some(Box::new(4)));
+    assert_eq!(iter.as_slice(), &[Box::new(2), Box::new(3)]);
+    *iter.as_mut_slice()[0] = 20;
+    assert_eq!(format!("{iter:?}"), "IntoIter([20, 3])");
+    assert_eq!(iter.next(), Some(Box::new(20)));
+    assert_eq!(iter.len(), 1);
+    // The remaining item is dropped with the iterator.
+    drop(iter);
+
+    let v = crate::vector![(), (), ()];
+    assert_eq!(v.into_iter().rev().count(), 3);
+
+    let mut total = 0;
+    for val in crate::vector![1u32, 2, 3] {
+        total += val;
+    }
+    assert_eq!(total, 6);
+
+    let mut iter = crate::Vector::<u32>::new().into_iter();
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
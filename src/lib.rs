@@ -1,18 +1,48 @@
-#![cfg_attr(feature = "nightly", feature(allocator_api))]
+#![cfg_attr(feature = "nightly", feature(allocator_api, trusted_len))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![doc = include_str!("../README.md")]
 
+// Named differently to avoid conflicting with the `alloc` module below.
+extern crate alloc as alloc_crate;
+
 mod raw;
 mod shared;
+mod shared_slice;
 mod vector;
 mod drain;
 mod splice;
+mod extract_if;
+mod growth;
+mod into_iter;
+mod small;
+mod fixed;
+mod array;
+mod uninit;
+mod windows_exact;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "rayon")]
+mod rayon_impls;
 
 pub use raw::{AtomicRefCount, BufferSize, DefaultRefCount, RefCount};
-pub use shared::{AtomicSharedVector, RefCountedVector, SharedVector};
+pub use shared::{empty, AtomicSharedVector, RefCountedVector, SharedVector, Weak};
+pub use shared_slice::SharedSlice;
 pub use vector::{Vector, RawVector, CollectIn};
+pub use into_iter::IntoIter;
+pub use extract_if::ExtractIf;
+pub use growth::{DoublingGrowth, ExactGrowth, GoldenRatioGrowth, GrowthStrategy};
+pub use small::SmallVector;
+pub use fixed::FixedVector;
+pub use array::ArrayVector;
+pub use uninit::UninitVector;
+pub use windows_exact::WindowsExact;
 
 pub mod alloc {
     pub use allocator_api2::alloc::{AllocError, Allocator, Global};
+    #[cfg(feature = "std")]
+    pub use crate::pool::PooledAllocator;
 }
 
 pub(crate) fn grow_amortized(len: usize, additional: usize) -> usize {
@@ -46,12 +76,12 @@ macro_rules! vector {
     });
     ([$($x:expr),*$(,)*] in $allocator:expr) => ({
         let count = 0usize $(+ $crate::vector!(@one@ $x))*;
-        let mut vec = $crate::Vector::try_with_capacity_in(count, $allocator).unwrap();
+        let mut vec = $crate::Vector::<_, _>::try_with_capacity_in(count, $allocator).unwrap();
         $(vec.push($x);)*
         vec
     });
     ([$x:expr;$n:expr] in $allocator:expr) => ({
-        let mut vec = $crate::Vector::try_with_capacity_in($n, $allocator).unwrap();
+        let mut vec = $crate::Vector::<_, _>::try_with_capacity_in($n, $allocator).unwrap();
         for _ in 0..$n { vec.push($x.clone()); }
         vec
     });
@@ -111,7 +141,7 @@ macro_rules! arc_vector {
 
 #[test]
 fn vector_macro() {
-    pub use allocator_api2::alloc::{Allocator, Global};
+    pub use allocator_api2::alloc::Global;
 
     let v1: Vector<u32> = vector![0, 1, 2, 3, 4, 5];
     let v2: Vector<u32> = vector![2; 4];
@@ -0,0 +1,158 @@
+use core::alloc::Layout;
+use core::cell::RefCell;
+use core::ptr::NonNull;
+
+use crate::alloc::{AllocError, Allocator, Global};
+
+/// An allocator that keeps freed buffers in a thread-local pool and reuses them for
+/// later allocations.
+///
+/// Allocation sizes are rounded up to the next power of two, so a pooled buffer is
+/// reused by any allocation with the same alignment that rounds up to the same size.
+/// At most `max_pool_size` buffers are kept per thread, extra buffers are released
+/// to the global allocator. The buffers pooled by a thread are released when the
+/// thread exits, or earlier with [`drain_pool`](Self::drain_pool).
+///
+/// ```
+/// use shared_vector::Vector;
+/// use shared_vector::alloc::{Global, PooledAllocator};
+///
+/// let pool = PooledAllocator::new(8);
+/// for _ in 0..100 {
+///     // Only the first iteration allocates, the other ones reuse the pooled buffer.
+///     let mut v: Vector<u64, PooledAllocator> = Vector::with_capacity_in(1024, pool);
+///     v.extend(0..1024);
+/// }
+/// PooledAllocator::drain_pool();
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct PooledAllocator {
+    max_pool_size: usize,
+}
+
+struct Pool {
+    buffers: Vec<(NonNull<u8>, Layout)>,
+}
+
+impl Pool {
+    fn release_all(&mut self) {
+        for (ptr, layout) in self.buffers.drain(..) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        self.release_all();
+    }
+}
+
+std::thread_local! {
+    static POOL: RefCell<Pool> = const { RefCell::new(Pool { buffers: Vec::new() }) };
+}
+
+impl PooledAllocator {
+    /// Creates an allocator that pools up to `max_pool_size` buffers per thread.
+    pub const fn new(max_pool_size: usize) -> Self {
+        PooledAllocator { max_pool_size }
+    }
+
+    /// Releases all of the buffers pooled by the current thread.
+    pub fn drain_pool() {
+        POOL.with(|pool| pool.borrow_mut().release_all());
+    }
+
+    #[cfg(test)]
+    fn pool_len() -> usize {
+        POOL.with(|pool| pool.borrow().buffers.len())
+    }
+}
+
+// The size class of a buffer. Any size between a requested size and its class rounds up
+// to the same class, which lets `deallocate` recover the layout of the underlying buffer.
+fn size_class(layout: Layout) -> Option<Layout> {
+    let size = layout.size().checked_next_power_of_two()?;
+    Layout::from_size_align(size, layout.align()).ok()
+}
+
+unsafe impl Allocator for PooledAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Global.allocate(layout);
+        }
+
+        let class = size_class(layout).ok_or(AllocError)?;
+
+        let pooled = POOL.with(|pool| {
+            let buffers = &mut pool.borrow_mut().buffers;
+            let idx = buffers.iter().position(|(_, l)| *l == class)?;
+            Some(buffers.swap_remove(idx).0)
+        });
+
+        let ptr = match pooled {
+            Some(ptr) => ptr,
+            None => Global.allocate(class)?.cast(),
+        };
+
+        Ok(NonNull::slice_from_raw_parts(ptr, class.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return Global.deallocate(ptr, layout);
+        }
+
+        let class = size_class(layout).unwrap();
+
+        let pooled = POOL.try_with(|pool| {
+            let buffers = &mut pool.borrow_mut().buffers;
+            if buffers.len() >= self.max_pool_size {
+                return false;
+            }
+            buffers.push((ptr, class));
+            true
+        });
+
+        if pooled != Ok(true) {
+            Global.deallocate(ptr, class);
+        }
+    }
+}
+
+#[test]
+fn pooled_allocator() {
+    use crate::Vector;
+
+    let pool = PooledAllocator::new(2);
+
+    let mut a: Vector<u64, PooledAllocator> = Vector::with_capacity_in(1000, pool);
+    a.push(1);
+    let ptr = a.as_ptr();
+    drop(a);
+    assert_eq!(PooledAllocator::pool_len(), 1);
+
+    // A smaller allocation in the same size class reuses the buffer.
+    let mut b: Vector<u64, PooledAllocator> = Vector::with_capacity_in(900, pool);
+    assert_eq!(b.as_ptr(), ptr);
+    assert!(b.capacity() >= 1000);
+    assert_eq!(PooledAllocator::pool_len(), 0);
+
+    // Growing returns the old buffer to the pool.
+    b.extend(0..5000);
+    assert_eq!(PooledAllocator::pool_len(), 1);
+
+    let c: Vector<u64, PooledAllocator> = Vector::with_capacity_in(10, pool);
+    let d: Vector<u64, PooledAllocator> = Vector::with_capacity_in(10, pool);
+    drop(b);
+    drop(c);
+    drop(d);
+    assert_eq!(PooledAllocator::pool_len(), 2);
+
+    PooledAllocator::drain_pool();
+    assert_eq!(PooledAllocator::pool_len(), 0);
+
+    let no_pool = PooledAllocator::new(0);
+    drop(Vector::<u8, PooledAllocator>::with_capacity_in(10, no_pool));
+    assert_eq!(PooledAllocator::pool_len(), 0);
+}
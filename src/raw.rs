@@ -5,18 +5,55 @@ use core::mem;
 use core::ptr::{self, NonNull};
 use core::sync::atomic::{
     AtomicI32,
-    Ordering::{Relaxed, Release},
+    Ordering::{Acquire, Relaxed, Release, SeqCst},
 };
 
-pub use crate::alloc::{AllocError, Allocator, Global};
+pub use crate::alloc::{AllocError, Allocator};
+#[cfg(test)]
+use crate::alloc::Global;
 
 pub type BufferSize = u32;
 
 pub trait RefCount {
+    /// Increments the reference count.
+    ///
+    /// # Safety
+    ///
+    /// The caller must hold a reference that keeps the count alive.
     unsafe fn add_ref(&self);
+    /// Decrements the reference count and returns true if it reached zero.
+    ///
+    /// # Safety
+    ///
+    /// The caller must give up the reference that is being released.
     unsafe fn release_ref(&self) -> bool;
     fn new(count: i32) -> Self;
     fn get(&self) -> i32;
+
+    /// Returns the count with the ordering that std's `Arc` uses to report it, so that counts
+    /// read by users are consistent with the other sequentially consistent operations.
+    fn get_seq_cst(&self) -> i32 {
+        self.get()
+    }
+
+    /// Increments the reference count unless it is zero, and returns true if it was incremented.
+    ///
+    /// # Safety
+    ///
+    /// The caller must hold a reference that keeps the memory of the count alive.
+    unsafe fn try_add_ref(&self) -> bool {
+        if self.get() <= 0 {
+            return false;
+        }
+        self.add_ref();
+        true
+    }
+
+    /// Returns true if this strong count is one and `weak` only holds the implicit weak
+    /// reference shared by the strong references.
+    fn is_unique(&self, weak: &Self) -> bool {
+        self.get() == 1 && weak.get() == 1
+    }
 }
 
 pub struct DefaultRefCount(UnsafeCell<i32>);
@@ -37,6 +74,8 @@ impl VecHeader {
 pub struct Header<R, A> {
     pub(crate) vec: VecHeader,
     pub(crate) ref_count: R,
+    // Weak references, plus one shared by all of the strong references.
+    pub(crate) weak_count: R,
     pub(crate) allocator: A,
 }
 
@@ -62,6 +101,30 @@ impl RefCount for AtomicRefCount {
     fn get(&self) -> i32 {
         self.0.load(Relaxed)
     }
+
+    #[inline]
+    fn get_seq_cst(&self) -> i32 {
+        self.0.load(SeqCst)
+    }
+
+    #[inline]
+    unsafe fn try_add_ref(&self) -> bool {
+        self.0
+            .fetch_update(Acquire, Relaxed, |n| if n <= 0 { None } else { Some(n + 1) })
+            .is_ok()
+    }
+
+    fn is_unique(&self, weak: &Self) -> bool {
+        // Like std's Arc, lock the weak count while reading the strong count so that another
+        // thread can't upgrade a weak reference between the two reads. A locked weak count
+        // makes `try_add_ref` fail, so downgrading has to wait for the lock to be released.
+        if self.0.load(Relaxed) != 1 || weak.0.compare_exchange(1, -1, Acquire, Relaxed).is_err() {
+            return false;
+        }
+        let unique = self.0.load(Acquire) == 1;
+        weak.0.store(1, Release);
+        unique
+    }
 }
 
 impl RefCount for DefaultRefCount {
@@ -96,7 +159,8 @@ pub unsafe fn data_ptr<Header, T>(header: NonNull<Header>) -> *mut T {
 pub(crate) const fn header_size<Header, T>() -> usize {
     let a = mem::align_of::<T>();
     let s = mem::size_of::<Header>();
-    let size = if a > s { a } else { s };
+    // Round the header size up to the alignment of T so that the items are correctly aligned.
+    let size = (s + a - 1) & !(a - 1);
 
     // Favor L1 cache line alignment for large structs.
     let min = if mem::size_of::<T>() < 64 { 16 } else { 64 };
@@ -120,11 +184,9 @@ pub fn buffer_layout<Header, T>(n: usize) -> Result<Layout, AllocError> {
     Layout::from_size_align(header_size + size, align).map_err(|_| AllocError)
 }
 
-pub unsafe fn drop_items<T>(mut ptr: *mut T, count: u32) {
-    for _ in 0..count {
-        core::ptr::drop_in_place(ptr);
-        ptr = ptr.add(1);
-    }
+pub unsafe fn drop_items<T>(ptr: *mut T, count: u32) {
+    // Unlike a loop, this keeps dropping the other items if one of the destructors panics.
+    core::ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, count as usize));
 }
 
 pub unsafe fn dealloc<T, R, A: Allocator>(mut ptr: NonNull<Header<R, A>>, cap: BufferSize) {
@@ -189,7 +251,7 @@ pub unsafe fn move_data<T>(src_data: *mut T, src_vec: &mut VecHeader, dst_data:
     }
 }
 
-pub unsafe fn extend_from_slice_assuming_capacity<T: Clone>(data: *mut T, vec: &mut VecHeader, slice: &[T])
+pub unsafe fn extend_from_slice_assuming_capacity<T>(data: *mut T, vec: &mut VecHeader, slice: &[T])
 where
     T: Clone,
 {
@@ -232,7 +294,8 @@ pub unsafe fn extend_within_capacity<T, I: Iterator<Item = T>>(data: *mut T, vec
     }
 
     vec.len += count;
-    return finished;
+
+    finished
 }
 
 #[inline]
@@ -258,6 +321,49 @@ pub unsafe fn clear<T>(data: *mut T, vec: &mut VecHeader) {
     vec.len = 0;
 }
 
+// The buffer shared by all empty vectors created with `RefCountedVector::empty`.
+//
+// It is never written to: its reference counts are not updated and it is never deallocated.
+// The strong count is high enough for the buffer to never be considered unique, so mutating
+// methods allocate a new buffer. It is large and aligned enough for `data_ptr` to stay within
+// the buffer for items with an alignment of up to 128 bytes.
+#[repr(C, align(128))]
+struct EmptyBuffer {
+    vec: VecHeader,
+    ref_count: i32,
+    weak_count: i32,
+}
+
+const EMPTY_BUFFER_SIZE: usize = mem::size_of::<EmptyBuffer>();
+
+static EMPTY_BUFFER: EmptyBuffer = EmptyBuffer {
+    vec: VecHeader { cap: 0, len: 0 },
+    ref_count: i32::MAX / 2,
+    weak_count: 1,
+};
+
+/// Returns the empty buffer if it can stand in for a buffer of `T` with header `Header<R, A>`.
+///
+/// Zero-sized allocators don't need to be stored, so any of them can share the buffer.
+pub(crate) fn empty_buffer<T, R, A>() -> Option<NonNull<Header<R, A>>> {
+    let fits = mem::size_of::<A>() == 0
+        && mem::size_of::<Header<R, A>>() == mem::offset_of!(EmptyBuffer, weak_count) + mem::size_of::<i32>()
+        && mem::align_of::<Header<R, A>>() <= mem::align_of::<i32>()
+        && mem::align_of::<T>() <= mem::align_of::<EmptyBuffer>()
+        && header_size::<Header<R, A>, T>() <= EMPTY_BUFFER_SIZE;
+    if !fits {
+        return None;
+    }
+
+    // The pointer is only ever read from.
+    NonNull::new(ptr::addr_of!(EMPTY_BUFFER) as *mut Header<R, A>)
+}
+
+#[inline]
+pub(crate) fn is_empty_buffer<R, A>(ptr: NonNull<Header<R, A>>) -> bool {
+    ptr::eq(ptr.as_ptr() as *const u8, ptr::addr_of!(EMPTY_BUFFER) as *const u8)
+}
+
 pub fn assert_ref_count_layout<R>() {
     assert_eq!(mem::size_of::<R>(), mem::size_of::<i32>());
     assert_eq!(mem::align_of::<R>(), mem::align_of::<i32>());
@@ -282,12 +388,9 @@ where
     let layout = buffer_layout::<Header<DefaultRefCount, A>, T>(cap)?;
     let allocation = allocator.allocate(layout)?;
     let items_size = allocation.len() - header_size::<Header<DefaultRefCount, A>, T>();
-    let size_of = mem::size_of::<T>();
-    let real_capacity = if size_of == 0 {
-        cap
-    } else {
-        items_size / size_of
-    };
+    // Zero-sized items don't take any space in the buffer, so the capacity is only limited
+    // by the type used to store the length.
+    let real_capacity = items_size.checked_div(mem::size_of::<T>()).unwrap_or(BufferSize::MAX as usize);
 
     Ok((allocation.cast(), real_capacity))
 }
@@ -306,3 +409,38 @@ fn buffer_layout_alignemnt() {
 
     assert_eq!(layout, atomic_layout);
 }
+
+#[test]
+fn over_aligned_items() {
+    use crate::Vector;
+    use core::alloc::Layout;
+
+    #[repr(align(32))]
+    #[derive(Clone, Debug, PartialEq)]
+    struct Simd([u8; 32]);
+
+    // An allocator with a large state, so that the header isn't a multiple of 32 bytes.
+    #[derive(Clone)]
+    struct BigAllocator(#[allow(dead_code)] [u8; 40]);
+    unsafe impl Allocator for BigAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            assert!(layout.align() >= mem::align_of::<Simd>());
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    assert_eq!(header_size::<Header<DefaultRefCount, BigAllocator>, Simd>() % 32, 0);
+    assert_eq!(header_size::<Header<DefaultRefCount, Global>, Simd>() % 32, 0);
+
+    let mut v: Vector<Simd, BigAllocator> = Vector::new_in(BigAllocator([0; 40]));
+    for i in 0..20 {
+        v.push(Simd([i; 32]));
+        assert_eq!(v.as_ptr() as usize % 32, 0);
+    }
+    let shared = v.into_shared();
+    assert_eq!(shared.as_slice().as_ptr() as usize % 32, 0);
+    assert_eq!(shared[19], Simd([19; 32]));
+}
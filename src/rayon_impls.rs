@@ -0,0 +1,76 @@
+use core::ops::RangeBounds;
+
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelDrainRange, ParallelIterator};
+
+use crate::alloc::{Allocator, Global};
+use crate::alloc_crate::vec::Vec;
+use crate::{GrowthStrategy, Vector};
+
+/// Moves the items into a `Vec` which is then split across threads.
+impl<T: Send, A: Allocator, G: GrowthStrategy> IntoParallelIterator for Vector<T, A, G> {
+    type Item = T;
+    type Iter = rayon::vec::IntoIter<T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        Vec::from(self).into_par_iter()
+    }
+}
+
+impl<'a, T: Sync + 'a, A: Allocator, G: GrowthStrategy> IntoParallelIterator for &'a Vector<T, A, G> {
+    type Item = &'a T;
+    type Iter = rayon::slice::Iter<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_slice().into_par_iter()
+    }
+}
+
+impl<'a, T: Send + 'a, A: Allocator, G: GrowthStrategy> IntoParallelIterator for &'a mut Vector<T, A, G> {
+    type Item = &'a mut T;
+    type Iter = rayon::slice::IterMut<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_mut_slice().into_par_iter()
+    }
+}
+
+/// The drained items are moved into a `Vec` which is then split across threads.
+/// Only the consumption of the items runs in parallel.
+impl<'a, T: Send + 'a, A: Allocator, G: GrowthStrategy> ParallelDrainRange<usize> for &'a mut Vector<T, A, G> {
+    type Item = T;
+    type Iter = rayon::vec::IntoIter<T>;
+
+    fn par_drain<R: RangeBounds<usize>>(self, range: R) -> Self::Iter {
+        self.drain(range).collect::<Vec<T>>().into_par_iter()
+    }
+}
+
+impl<T: Send> FromParallelIterator<T> for Vector<T, Global> {
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        Vector::from(par_iter.into_par_iter().collect::<Vec<T>>())
+    }
+}
+
+#[test]
+fn par_iter() {
+    use rayon::prelude::*;
+
+    let mut v: Vector<u32> = (0..1000).into_par_iter().collect();
+    assert_eq!(v.len(), 1000);
+    assert!(v.iter().copied().eq(0..1000));
+
+    assert_eq!(v.par_iter().sum::<u32>(), (0..1000).sum());
+
+    v.par_iter_mut().for_each(|x| *x *= 2);
+    assert_eq!(v[10], 20);
+
+    let drained: Vector<u32> = v.par_drain(990..).collect();
+    assert_eq!(drained.as_slice(), &[1980, 1982, 1984, 1986, 1988, 1990, 1992, 1994, 1996, 1998]);
+    assert_eq!(v.len(), 990);
+
+    let total: u32 = v.into_par_iter().map(|x| x / 2).sum();
+    assert_eq!(total, (0..990).sum());
+}
@@ -0,0 +1,62 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::alloc::{Allocator, Global};
+use crate::{AtomicSharedVector, GrowthStrategy, RefCount, RefCountedVector, SharedVector, Vector};
+
+impl<T: Serialize, A: Allocator, G: GrowthStrategy> Serialize for Vector<T, A, G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<T: Serialize, R: RefCount, A: Allocator> Serialize for RefCountedVector<T, R, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+struct VectorVisitor<T, G>(PhantomData<(T, G)>);
+
+impl<'de, T: Deserialize<'de>, G: GrowthStrategy> Visitor<'de> for VectorVisitor<T, G> {
+    type Value = Vector<T, Global, G>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        // Don't trust the size hint blindly, it comes from the (potentially malicious) input.
+        const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+        let max_items = MAX_PREALLOC_BYTES / core::mem::size_of::<T>().max(1);
+        let cap = seq.size_hint().unwrap_or(0).min(max_items);
+
+        let mut vector = Vector::with_capacity_in(cap, Global);
+        while let Some(item) = seq.next_element()? {
+            vector.push(item);
+        }
+
+        Ok(vector)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, G: GrowthStrategy> Deserialize<'de> for Vector<T, Global, G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(VectorVisitor(PhantomData))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SharedVector<T, Global> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vector::<T>::deserialize(deserializer).map(Vector::into_shared)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for AtomicSharedVector<T, Global> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vector::<T>::deserialize(deserializer).map(Vector::into_shared_atomic)
+    }
+}
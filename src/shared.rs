@@ -1,14 +1,19 @@
 use core::fmt::Debug;
-use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::ops::{Deref, DerefMut, Index, IndexMut, RangeBounds};
+use core::marker::PhantomData;
 use core::ptr::NonNull;
 use core::{mem, ptr};
 use core::sync::atomic::Ordering;
+use crate::alloc_crate::sync::Arc;
 
 use crate::raw;
+use crate::drain::Drain;
+use crate::into_iter::IntoIter;
 use crate::alloc::{AllocError, Allocator, Global};
 use crate::raw::{BufferSize, HeaderBuffer};
+use crate::shared_slice::SharedSlice;
 use crate::vector::{Vector, RawVector};
-use crate::{grow_amortized, AtomicRefCount, DefaultRefCount, RefCount};
+use crate::{grow_amortized, AtomicRefCount, DefaultRefCount, GrowthStrategy, RefCount};
 
 /// A heap allocated, atomically reference counted, immutable contiguous buffer containing elements of type `T`.
 ///
@@ -68,6 +73,24 @@ impl<T, R: RefCount> RefCountedVector<T, R, Global> {
 }
 
 impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
+    /// Converts a unique vector into a shared one without copying its items.
+    ///
+    /// The reference count is written into the space that every vector reserves in front
+    /// of its items, so this is O(1). It only allocates if `vec` has not allocated yet.
+    /// This is the same conversion as [`Vector::into_shared`] and [`Vector::into_shared_atomic`].
+    pub fn freeze<G: GrowthStrategy>(vec: Vector<T, A, G>) -> Self {
+        if vec.capacity() == 0 {
+            // Nothing to release, move the allocator out.
+            let vec = mem::ManuallyDrop::new(vec);
+            let allocator = unsafe { ptr::read(vec.allocator()) };
+            return Self::try_with_capacity_in(0, allocator).unwrap();
+        }
+
+        unsafe {
+            RefCountedVector { inner: vec.into_header_buffer::<R>() }
+        }
+    }
+
     /// Creates an empty vector without allocating memory.
     pub fn new_in(allocator: A) -> Self {
         Self::try_with_capacity_in(0, allocator).unwrap()
@@ -93,6 +116,7 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
                         len: 0,
                     },
                     ref_count: R::new(1),
+                    weak_count: R::new(1),
                     allocator,
                 },
             );
@@ -149,13 +173,30 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
     #[inline]
     pub fn new_ref(&self) -> Self {
         unsafe {
-            self.inner.as_ref().ref_count.add_ref();
+            if !raw::is_empty_buffer(self.inner.header) {
+                self.inner.as_ref().ref_count.add_ref();
+            }
             RefCountedVector {
                 inner: HeaderBuffer::from_raw(self.inner.header)
             }
         }
     }
 
+    /// Returns a handle to the items in `range` that shares this vector's buffer, without copying them.
+    ///
+    /// See [`SharedSlice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn slice<Range>(&self, range: Range) -> SharedSlice<T, R, A>
+    where
+        Range: RangeBounds<usize>,
+    {
+        SharedSlice::new(self.new_ref(), range)
+    }
+
     /// Extracts a slice containing the entire vector.
     #[inline]
     pub fn as_slice(&self) -> &[T] {
@@ -166,11 +207,132 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
 
     /// Returns true if this is the only existing handle to the buffer.
     ///
+    /// Weak handles count: the buffer is not unique while a [`Weak`] reference to it exists.
     /// When this function returns true, mutable methods and converting to a `Vector`
     /// is very fast (does not involve additional memory allocations or copies).
     #[inline]
     pub fn is_unique(&self) -> bool {
-        unsafe { self.inner.as_ref().ref_count.get() == 1 }
+        unsafe {
+            let header = self.inner.as_ref();
+            header.ref_count.is_unique(&header.weak_count)
+        }
+    }
+
+    /// Returns true if this vector points to the static buffer shared by the empty vectors
+    /// created with [`empty`](Self::empty).
+    ///
+    /// This buffer was not allocated with the vector's allocator and is never deallocated.
+    #[inline]
+    pub fn is_empty_singleton(&self) -> bool {
+        raw::is_empty_buffer(self.inner.header)
+    }
+
+    /// Returns the number of handles to this vector's buffer.
+    ///
+    /// The handles to the [empty singleton](Self::is_empty_singleton) are not counted, so it
+    /// always reports one.
+    ///
+    /// This is an associated function so that it doesn't shadow a method of `T`, like
+    /// [`Arc::strong_count`]. With atomic reference counting, other threads can change the count
+    /// at any time.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        if this.is_empty_singleton() {
+            return 1;
+        }
+
+        unsafe { this.inner.as_ref().ref_count.get_seq_cst() as usize }
+    }
+
+    /// Returns the number of [`Weak`] references to this vector's buffer.
+    ///
+    /// This is an associated function, like [`strong_count`](Self::strong_count).
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        let count = unsafe { this.inner.as_ref().weak_count.get_seq_cst() };
+        // A negative count means `is_unique` locked it, which it only does when there are no weak references.
+        if count <= 0 { 0 } else { count as usize - 1 }
+    }
+
+    /// Creates a [`Weak`] reference to this vector's buffer.
+    ///
+    /// The weak reference does not keep the items alive, see [`Weak::upgrade`].
+    pub fn downgrade(this: &Self) -> Weak<T, R, A> {
+        unsafe {
+            let weak_count = &this.inner.as_ref().weak_count;
+            while !raw::is_empty_buffer(this.inner.header) && !weak_count.try_add_ref() {
+                // The count is locked by `is_unique`.
+                core::hint::spin_loop();
+            }
+        }
+
+        Weak { header: this.inner.header, _marker: PhantomData }
+    }
+
+    /// Returns a mutable slice of the vector's content if this is the only existing handle
+    /// to the buffer, or `None` otherwise.
+    ///
+    /// Unlike `as_mut_slice` and `make_unique`, this never copies the buffer.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut [T]> {
+        if !self.is_unique() {
+            return None;
+        }
+
+        unsafe {
+            Some(core::slice::from_raw_parts_mut(self.data_ptr(), self.len()))
+        }
+    }
+
+    /// Converts this vector into a unique `Vector` without copying, if this is the only
+    /// existing handle to the buffer.
+    ///
+    /// Returns `None` (and drops this handle) if there are other references to the buffer.
+    /// See also `into_unique` which copies the buffer instead.
+    pub fn try_unique(self) -> Option<Vector<T, A>> {
+        if !self.is_unique() {
+            return None;
+        }
+
+        unsafe {
+            let mut header = self.vec_header().clone();
+            let allocator = ptr::read(self.allocator());
+            let data = if mem::size_of::<T>() == 0 {
+                // Unique vectors of zero-sized items don't allocate, only the header is released.
+                let layout = raw::buffer_layout::<raw::Header<R, A>, T>(header.cap as usize).unwrap();
+                allocator.deallocate(self.inner.header.cast(), layout);
+                header.cap = BufferSize::MAX;
+                NonNull::dangling()
+            } else {
+                NonNull::new_unchecked(self.data_ptr())
+            };
+
+            mem::forget(self);
+
+            Some(Vector {
+                raw: RawVector {
+                    data,
+                    header,
+                },
+                allocator,
+                growth: PhantomData,
+            })
+        }
+    }
+
+    /// Converts this vector into a unique `Vector` in O(1) if this is the only existing
+    /// handle to the buffer, or returns it unchanged otherwise.
+    ///
+    /// This is the inverse of [`freeze`](Self::freeze). Unlike [`try_unique`](Self::try_unique)
+    /// the handle is given back on failure, so the caller can decide whether to copy the
+    /// items with [`into_unique`](Self::into_unique) or to keep sharing them. Prefer
+    /// [`make_unique`](Self::make_unique) when the goal is only to mutate the items in place.
+    pub fn thaw(self) -> Result<Vector<T, A>, Self> {
+        if !self.is_unique() {
+            return Err(self);
+        }
+
+        Ok(self.try_unique().unwrap())
     }
 
     /// Clears the vector, removing all values.
@@ -178,6 +340,10 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
     where
         A: Clone,
     {
+        if self.is_empty_singleton() {
+            return;
+        }
+
         if self.is_unique() {
             unsafe {
                 raw::clear(self.data_ptr(), self.vec_header_mut());
@@ -194,6 +360,13 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
         self.inner.header == other.inner.header
     }
 
+    /// Returns true if the two vectors start at the same address and have the same length.
+    ///
+    /// Like comparing slices by pointer, this ignores the capacity and the items themselves.
+    pub fn data_ptr_eq(&self, other: &Self) -> bool {
+        self.data_ptr() == other.data_ptr() && self.len() == other.len()
+    }
+
     /// Allocates a duplicate of this buffer (infallible).
     pub fn copy_buffer(&self) -> Self
     where
@@ -230,6 +403,84 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
         }
     }
 
+    /// Applies `f` to each item and collects the results into a new shared vector in the
+    /// same allocator.
+    ///
+    /// This vector is left untouched, so this always allocates a new buffer with the length
+    /// of this vector as capacity.
+    ///
+    /// ```
+    /// use shared_vector::SharedVector;
+    /// let words = SharedVector::from_slice(&["a", "bcd", ""]);
+    /// let lengths = words.map(|s| s.len());
+    /// assert_eq!(lengths.as_slice(), &[1, 3, 0]);
+    /// assert_eq!(words.len(), 3);
+    /// ```
+    pub fn map<U, F>(&self, mut f: F) -> RefCountedVector<U, R, A>
+    where
+        F: FnMut(&T) -> U,
+        A: Clone,
+    {
+        let mut result: Vector<U, A> = Vector::with_capacity_in(self.len(), self.allocator().clone());
+        for item in self.as_slice() {
+            let mapped = f(item);
+            unsafe {
+                let idx = result.len();
+                ptr::write(result.as_mut_ptr().add(idx), mapped);
+                result.set_len(idx + 1);
+            }
+        }
+
+        RefCountedVector::freeze(result)
+    }
+
+    /// Applies `f` to each item and collects the `Some` results into a new shared vector in
+    /// the same allocator.
+    ///
+    /// The new buffer is allocated with the length of this vector as capacity, and shrunk to
+    /// fit the results if some of the items were skipped.
+    ///
+    /// ```
+    /// use shared_vector::SharedVector;
+    /// let lines = SharedVector::from_slice(&["3", "x", "10", "", "7"]);
+    /// let values = lines.filter_map(|line| line.parse::<u32>().ok());
+    /// let large = values.filter(|v| *v > 5);
+    /// assert_eq!(values.as_slice(), &[3, 10, 7]);
+    /// assert_eq!(large.as_slice(), &[10, 7]);
+    /// ```
+    pub fn filter_map<U, F>(&self, mut f: F) -> RefCountedVector<U, R, A>
+    where
+        F: FnMut(&T) -> Option<U>,
+        A: Clone,
+    {
+        let mut result: Vector<U, A> = Vector::with_capacity_in(self.len(), self.allocator().clone());
+        for item in self.as_slice() {
+            if let Some(mapped) = f(item) {
+                unsafe {
+                    let idx = result.len();
+                    ptr::write(result.as_mut_ptr().add(idx), mapped);
+                    result.set_len(idx + 1);
+                }
+            }
+        }
+        result.shrink_to_fit();
+
+        RefCountedVector::freeze(result)
+    }
+
+    /// Clones the items for which `predicate` returns `true` into a new shared vector in the
+    /// same allocator.
+    ///
+    /// See [`filter_map`](Self::filter_map).
+    pub fn filter<F>(&self, mut predicate: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+        T: Clone,
+        A: Clone,
+    {
+        self.filter_map(|item| if predicate(item) { Some(item.clone()) } else { None })
+    }
+
     #[inline]
     pub fn data_ptr(&self) -> *mut T {
         unsafe { (self.inner.as_ptr() as *mut u8).add(raw::header_size::<raw::Header<R, A>, T>()) as *mut T }
@@ -245,6 +496,36 @@ impl<T, R: RefCount, A: Allocator> RefCountedVector<T, R, A> {
     }
 }
 
+impl<T, R: RefCount, A: Allocator + Default> RefCountedVector<T, R, A> {
+    /// Returns an empty vector without allocating.
+    ///
+    /// Empty vectors created this way point to a single static buffer, whose reference count
+    /// is never updated and which is never deallocated. This buffer is never unique, so the
+    /// first mutation allocates a new buffer. Falls back to allocating an empty buffer with
+    /// `A::default()` if the allocator is not zero-sized or if the alignment of `T` is larger
+    /// than 128 bytes.
+    ///
+    /// See also [`empty`](crate::empty) and [`is_empty_singleton`](Self::is_empty_singleton).
+    pub fn empty() -> Self {
+        match raw::empty_buffer::<T, R, A>() {
+            Some(header) => {
+                // Zero-sized allocators carry no state, so the one created here is equivalent to
+                // the one the buffer would store.
+                mem::forget(A::default());
+                RefCountedVector { inner: unsafe { HeaderBuffer::from_raw(header) } }
+            }
+            None => Self::new_in(A::default()),
+        }
+    }
+}
+
+/// Returns an empty shared vector without allocating.
+///
+/// See [`RefCountedVector::empty`].
+pub fn empty<T>() -> SharedVector<T> {
+    SharedVector::empty()
+}
+
 /// Mutable methods that can cause the vector to be cloned and therefore require both the items and
 /// the allocator to be cloneable.
 impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
@@ -253,21 +534,8 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
     pub fn into_unique(mut self) -> Vector<T, A> {
         self.ensure_unique();
 
-        unsafe {
-            let data = NonNull::new_unchecked(self.data_ptr());
-            let header = self.vec_header().clone();
-            let allocator = self.inner.as_ref().allocator.clone();
-
-            mem::forget(self);
-
-            Vector {
-                raw: RawVector {
-                    data,
-                    header,
-                },
-                allocator,
-            }
-        }
+        // The buffer is unique now, so the items can be moved out without copying them again.
+        self.try_unique().unwrap()
     }
 
     /// Appends an element to the back of a collection.
@@ -278,7 +546,7 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
     pub fn push(&mut self, val: T) {
         self.reserve(1);
         unsafe {
-            raw::push_assuming_capacity(self.data_ptr(), &mut self.vec_header_mut(), val);
+            raw::push_assuming_capacity(self.data_ptr(), self.vec_header_mut(), val);
         }
     }
 
@@ -287,7 +555,35 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
         self.ensure_unique();
 
         unsafe {
-            raw::pop(self.data_ptr(), &mut self.vec_header_mut())
+            raw::pop(self.data_ptr(), self.vec_header_mut())
+        }
+    }
+
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
+    ///
+    /// This preserves the order of the remaining elements and runs in *O*(*n*).
+    /// Use [`swap_remove`](Self::swap_remove) if the order does not matter.
+    ///
+    /// Like other mutable operations, this method may reallocate if the vector is not unique.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.ensure_unique();
+
+        let len = self.len();
+        assert!(index < len, "removal index (is {index}) should be < len (is {len})");
+
+        unsafe {
+            let ptr = self.data_ptr().add(index);
+            let item = ptr::read(ptr);
+            ptr::copy(ptr.add(1), ptr, len - index - 1);
+
+            self.vec_header_mut().len = (len - 1) as BufferSize;
+
+            item
         }
     }
 
@@ -295,6 +591,9 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
     ///
     /// The removed element is replaced by the last element of the vector.
     ///
+    /// This does not preserve the order of the remaining elements, but runs in *O*(1).
+    /// Use [`remove`](Self::remove) to preserve the order.
+    ///
     /// # Panics
     ///
     /// Panics if index is out of bounds.
@@ -303,7 +602,7 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
         self.ensure_unique();
 
         let len = self.len();
-        assert!(idx < len);
+        assert!(idx < len, "swap_remove index (is {idx}) should be < len (is {len})");
 
         unsafe {
             let data_ptr = self.data_ptr();
@@ -335,7 +634,7 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
 
         self.ensure_unique();
         unsafe {
-            raw::push_assuming_capacity(self.data_ptr(), &mut self.vec_header_mut(), val);
+            raw::push_assuming_capacity(self.data_ptr(), self.vec_header_mut(), val);
         }
 
         Ok(())
@@ -381,6 +680,18 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
         }
     }
 
+    /// Returns a mutable slice of the vector's content, cloning the buffer first
+    /// if there are other references to it.
+    ///
+    /// This is the copy-on-write counterpart of `get_mut`.
+    #[inline]
+    pub fn make_unique(&mut self) -> &mut [T] {
+        self.ensure_unique();
+        unsafe {
+            core::slice::from_raw_parts_mut(self.data_ptr(), self.len())
+        }
+    }
+
     /// Extracts a mutable slice containing the entire vector.
     ///
     /// Like other mutable methods, this will clone the vector's storage
@@ -397,6 +708,29 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
         }
     }
 
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator. If the iterator is dropped before
+    /// being fully consumed, it drops the remaining removed elements.
+    ///
+    /// Like other mutable methods, this will clone the vector's storage
+    /// if it is not unique, leaving the other handles untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn drain<Range>(&mut self, range: Range) -> Drain<'_, T>
+    where
+        Range: RangeBounds<usize>,
+    {
+        self.ensure_unique();
+        unsafe {
+            let data = NonNull::new_unchecked(self.data_ptr());
+            let header = NonNull::from(self.vec_header_mut());
+            Drain::new(data, header, range)
+        }
+    }
+
     /// Allocates a duplicate of this buffer (infallible).
     pub fn clone_buffer(&self) -> Self
     where
@@ -532,7 +866,12 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
     /// Moves all the elements of `other` into `self`, leaving `other` empty.
     ///
     /// If `other is not unique, the elements are cloned instead of moved.
+    /// Does not allocate if `other` is empty.
     pub fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+
         self.reserve(other.len());
 
         unsafe {
@@ -595,7 +934,11 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
                 }?;
 
                 self.inner.header = new_alloc.cast();
-                self.inner.as_mut().vec.cap = new_cap as BufferSize;
+                self.inner.as_mut().vec.cap = if mem::size_of::<T>() == 0 {
+                    BufferSize::MAX
+                } else {
+                    new_cap as BufferSize
+                };
 
                 return Ok(());
             }
@@ -612,6 +955,40 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
     }
 
 
+    /// Returns a new vector containing the items of `a` followed by the items of `b`.
+    ///
+    /// The items are cloned into a single allocation of exactly `a.len() + b.len()` items,
+    /// using the allocator of `a`. See [`concat_copy`](Self::concat_copy) for `Copy` items.
+    pub fn concat(a: &Self, b: &Self) -> Self {
+        let mut result = Self::with_capacity_in(a.len() + b.len(), a.allocator().clone());
+        unsafe {
+            raw::extend_from_slice_assuming_capacity(result.data_ptr(), result.vec_header_mut(), a.as_slice());
+            raw::extend_from_slice_assuming_capacity(result.data_ptr(), result.vec_header_mut(), b.as_slice());
+        }
+
+        result
+    }
+
+    /// Returns a new vector containing the items of `a` followed by the items of `b`.
+    ///
+    /// Like [`concat`](Self::concat), but the items are copied in bulk rather than cloned
+    /// one by one.
+    pub fn concat_copy(a: &Self, b: &Self) -> Self
+    where
+        T: Copy,
+    {
+        let len = a.len() + b.len();
+        let mut result = Self::with_capacity_in(len, a.allocator().clone());
+        unsafe {
+            let dst = result.data_ptr();
+            ptr::copy_nonoverlapping(a.data_ptr(), dst, a.len());
+            ptr::copy_nonoverlapping(b.data_ptr(), dst.add(a.len()), b.len());
+            result.vec_header_mut().len = len as BufferSize;
+        }
+
+        result
+    }
+
     // TODO: remove this one?
     /// Returns the concatenation of two vectors.
     pub fn concatenate(mut self, mut other: Self) -> Self
@@ -623,10 +1000,61 @@ impl<T: Clone, R: RefCount, A: Allocator + Clone> RefCountedVector<T, R, A> {
 
         self
     }
+
+    // Moves the items into `dst` if this is the only handle to the buffer, clones them otherwise.
+    fn move_or_clone_into<A2: Allocator, G: GrowthStrategy>(mut self, dst: &mut Vector<T, A2, G>) {
+        if !self.is_unique() {
+            dst.extend_from_slice(self.as_slice());
+            return;
+        }
+
+        let len = self.len();
+        dst.reserve(len);
+        unsafe {
+            let dst_len = dst.len();
+            ptr::copy_nonoverlapping(self.data_ptr(), dst.as_mut_ptr().add(dst_len), len);
+            self.vec_header_mut().len = 0;
+            dst.set_len(dst_len + len);
+        }
+    }
+
+    // Reuses the buffer if it is unique, since both reference count types have the same layout.
+    fn convert_ref_count<R2: RefCount>(self) -> RefCountedVector<T, R2, A> {
+        if !self.is_unique() {
+            return RefCountedVector::try_from_slice_in(self.as_slice(), self.allocator().clone()).unwrap();
+        }
+
+        raw::assert_ref_count_layout::<R2>();
+        unsafe {
+            let header: NonNull<raw::Header<R2, A>> = self.inner.header.cast();
+            ptr::write(ptr::addr_of_mut!((*header.as_ptr()).ref_count), R2::new(1));
+            ptr::write(ptr::addr_of_mut!((*header.as_ptr()).weak_count), R2::new(1));
+
+            mem::forget(self);
+
+            RefCountedVector {
+                inner: HeaderBuffer::from_raw(header),
+            }
+        }
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> AtomicSharedVector<T, A> {
+    /// Converts this vector into a non-atomically reference counted one.
+    ///
+    /// This does not allocate if this is the only existing handle to the buffer,
+    /// otherwise the content is copied into a new buffer.
+    pub fn into_shared_vector(self) -> SharedVector<T, A> {
+        self.convert_ref_count()
+    }
 }
 
 impl<T, R: RefCount, A: Allocator> Drop for RefCountedVector<T, R, A> {
     fn drop(&mut self) {
+        if raw::is_empty_buffer(self.inner.header) {
+            return;
+        }
+
         unsafe {
             if self.inner.as_ref().ref_count.release_ref() {
                 let header = self.vec_header().clone();
@@ -636,14 +1064,115 @@ impl<T, R: RefCount, A: Allocator> Drop for RefCountedVector<T, R, A> {
                 core::sync::atomic::fence(Ordering::Acquire);
                 
                 raw::drop_items(self.data_ptr(), header.len);
-                raw::dealloc::<T, R, A>(self.inner.header, header.cap);
+                self.vec_header_mut().len = 0;
+
+                // Release the weak reference held by the strong references. The buffer stays
+                // allocated as long as there are weak references to its header.
+                if self.inner.as_ref().weak_count.release_ref() {
+                    core::sync::atomic::fence(Ordering::Acquire);
+                    raw::dealloc::<T, R, A>(self.inner.header, header.cap);
+                }
+            }
+        }
+    }
+}
+
+/// A non-owning reference to the buffer of a [`RefCountedVector`].
+///
+/// Weak references don't keep the items alive: they are dropped when the last strong reference
+/// goes away, after which [`upgrade`](Self::upgrade) returns `None`. The buffer itself is only
+/// deallocated when the last weak reference is dropped too. This is useful to break reference
+/// cycles, for example in caches.
+///
+/// Created with [`RefCountedVector::downgrade`].
+pub struct Weak<T, R: RefCount = DefaultRefCount, A: Allocator = Global> {
+    header: NonNull<raw::Header<R, A>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, R: RefCount, A: Allocator> Weak<T, R, A> {
+    /// Attempts to get a strong reference to the buffer.
+    ///
+    /// Returns `None` if all of the strong references were dropped.
+    pub fn upgrade(&self) -> Option<RefCountedVector<T, R, A>> {
+        unsafe {
+            if !raw::is_empty_buffer(self.header) && !self.header.as_ref().ref_count.try_add_ref() {
+                return None;
+            }
+
+            Some(RefCountedVector { inner: HeaderBuffer::from_raw(self.header) })
+        }
+    }
+
+    /// Returns the number of strong references to the buffer.
+    ///
+    /// Like [`RefCountedVector::strong_count`], this is always one for the empty singleton.
+    pub fn strong_count(&self) -> usize {
+        if raw::is_empty_buffer(self.header) {
+            return 1;
+        }
+
+        unsafe { self.header.as_ref().ref_count.get() as usize }
+    }
+
+    /// Returns the number of weak references to the buffer, or zero if there are no strong
+    /// references left.
+    pub fn weak_count(&self) -> usize {
+        unsafe {
+            let header = self.header.as_ref();
+            if header.ref_count.get() == 0 {
+                return 0;
             }
+            (header.weak_count.get() - 1).max(0) as usize
         }
     }
+
+    /// Returns true if the two weak references point to the same buffer.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.header == other.header
+    }
 }
 
+impl<T, R: RefCount, A: Allocator> Clone for Weak<T, R, A> {
+    fn clone(&self) -> Self {
+        if !raw::is_empty_buffer(self.header) {
+            unsafe {
+                self.header.as_ref().weak_count.add_ref();
+            }
+        }
+
+        Weak { header: self.header, _marker: PhantomData }
+    }
+}
 
-unsafe impl<T: Sync, A: Allocator + Send> Send for AtomicSharedVector<T, A> {}
+impl<T, R: RefCount, A: Allocator> Drop for Weak<T, R, A> {
+    fn drop(&mut self) {
+        if raw::is_empty_buffer(self.header) {
+            return;
+        }
+
+        unsafe {
+            if self.header.as_ref().weak_count.release_ref() {
+                core::sync::atomic::fence(Ordering::Acquire);
+                let cap = self.header.as_ref().vec.cap;
+                raw::dealloc::<T, R, A>(self.header, cap);
+            }
+        }
+    }
+}
+
+impl<T, R: RefCount, A: Allocator> Debug for Weak<T, R, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("(Weak)")
+    }
+}
+
+unsafe impl<T: Send + Sync, A: Allocator + Send + Sync> Send for Weak<T, AtomicRefCount, A> {}
+unsafe impl<T: Send + Sync, A: Allocator + Send + Sync> Sync for Weak<T, AtomicRefCount, A> {}
+
+
+unsafe impl<T: Send + Sync, A: Allocator + Send + Sync> Send for AtomicSharedVector<T, A> {}
+unsafe impl<T: Send + Sync, A: Allocator + Send + Sync> Sync for AtomicSharedVector<T, A> {}
 
 impl<T, R: RefCount, A: Allocator> Clone for RefCountedVector<T, R, A> {
     fn clone(&self) -> Self {
@@ -671,9 +1200,19 @@ impl<T, R: RefCount, A: Allocator> AsRef<[T]> for RefCountedVector<T, R, A> {
     }
 }
 
-impl<T, R: RefCount> Default for RefCountedVector<T, R, Global> {
+/// Returns the shared empty vector when possible, see [`RefCountedVector::empty`].
+impl<T, R: RefCount, A: Allocator + Default> Default for RefCountedVector<T, R, A> {
     fn default() -> Self {
-        Self::new()
+        Self::empty()
+    }
+}
+
+/// Iterates over the items by value, cloning them first if the buffer is shared.
+impl<T: Clone, R: RefCount, A: Allocator + Clone> IntoIterator for RefCountedVector<T, R, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+    fn into_iter(self) -> IntoIter<T, A> {
+        self.into_unique().into_iter()
     }
 }
 
@@ -738,18 +1277,104 @@ impl<T: Debug, R: RefCount, A: Allocator> Debug for RefCountedVector<T, R, A> {
     }
 }
 
+impl<T: Eq, R: RefCount, A: Allocator> Eq for RefCountedVector<T, R, A> {}
+
+impl<T: core::hash::Hash, R: RefCount, A: Allocator> core::hash::Hash for RefCountedVector<T, R, A> {
+    fn hash<H>(&self, state: &mut H) where H: core::hash::Hasher {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: Clone, R: RefCount> From<&[T]> for RefCountedVector<T, R, Global> {
+    fn from(slice: &[T]) -> Self {
+        Self::from_slice(slice)
+    }
+}
+
 impl<T: Clone, A: Allocator + Clone> From<Vector<T, A>> for SharedVector<T, A> {
     fn from(vector: Vector<T, A>) -> Self {
         vector.into_shared()
     }
 }
 
+/// Concatenates the vectors into a single allocation.
+///
+/// The items of unique vectors are moved, the items of shared ones are cloned.
+impl<T: Clone, R: RefCount> FromIterator<RefCountedVector<T, R, Global>> for RefCountedVector<T, R, Global> {
+    fn from_iter<I: IntoIterator<Item = RefCountedVector<T, R, Global>>>(iter: I) -> Self {
+        // Only the handles are buffered, so that the total length is known before allocating.
+        let parts: Vector<RefCountedVector<T, R, Global>> = iter.into_iter().collect();
+        let len = parts.iter().map(|part| part.len()).sum();
+
+        let mut result: Vector<T> = Vector::with_capacity(len);
+        for part in parts {
+            part.move_or_clone_into(&mut result);
+        }
+
+        RefCountedVector::freeze(result)
+    }
+}
+
+/// Appends the items of each vector.
+///
+/// The items of unique vectors are moved, the items of shared ones are cloned.
+/// Since [`Vector::extend`] takes items, this has to be called as `Extend::extend(&mut vector, iter)`.
+impl<T: Clone, R: RefCount, A: Allocator, A2: Allocator + Clone, G: GrowthStrategy> Extend<RefCountedVector<T, R, A2>> for Vector<T, A, G> {
+    fn extend<I: IntoIterator<Item = RefCountedVector<T, R, A2>>>(&mut self, iter: I) {
+        for part in iter {
+            part.move_or_clone_into(self);
+        }
+    }
+}
+
 impl<T: Clone, A: Allocator + Clone> From<Vector<T, A>> for AtomicSharedVector<T, A> {
     fn from(vector: Vector<T, A>) -> Self {
         vector.into_shared_atomic()
     }
 }
 
+impl<T: Clone, A: Allocator + Clone> From<SharedVector<T, A>> for AtomicSharedVector<T, A> {
+    fn from(vector: SharedVector<T, A>) -> Self {
+        vector.convert_ref_count()
+    }
+}
+
+impl<T: Clone, R: RefCount, A: Allocator> From<RefCountedVector<T, R, A>> for Arc<[T]> {
+    /// Moves the items into the `Arc` if the vector is unique, otherwise clones them.
+    fn from(vector: RefCountedVector<T, R, A>) -> Self {
+        if !vector.is_unique() {
+            return Arc::from(vector.as_slice());
+        }
+
+        let mut unique = vector.try_unique().unwrap();
+        unique.drain(..).collect()
+    }
+}
+
+impl<T, R: RefCount> TryFrom<Arc<[T]>> for RefCountedVector<T, R, Global> {
+    type Error = Arc<[T]>;
+
+    /// Moves the items out of the `Arc` if it has no other strong or weak references,
+    /// otherwise gives the `Arc` back.
+    fn try_from(mut arc: Arc<[T]>) -> Result<Self, Arc<[T]>> {
+        let len = match Arc::get_mut(&mut arc) {
+            Some(slice) => slice.len(),
+            None => return Err(arc),
+        };
+
+        let mut vector = Self::with_capacity(len);
+        unsafe {
+            ptr::copy_nonoverlapping(arc.as_ptr(), vector.data_ptr(), len);
+            vector.vec_header_mut().len = len as BufferSize;
+
+            // The items have been moved, only deallocate the Arc's storage.
+            drop(Arc::from_raw(Arc::into_raw(arc) as *const [mem::ManuallyDrop<T>]));
+        }
+
+        Ok(vector)
+    }
+}
+
 // In order to give us a chance to catch leaks and double-frees, test with values that implement drop.
 #[cfg(test)]
 fn num(val: u32) -> Box<u32> {
@@ -846,3 +1471,404 @@ fn shrink_to_zero() {
     let mut v: SharedVector<u32> = SharedVector::new();
     v.shrink_to(0);
 }
+
+#[test]
+fn try_unique() {
+    let a: SharedVector<Box<u32>> = SharedVector::from(&[num(1), num(2)][..]);
+    let b = a.new_ref();
+    assert!(b.try_unique().is_none());
+
+    let mut v = a.try_unique().unwrap();
+    v.push(num(3));
+    assert_eq!(v.as_slice(), &[num(1), num(2), num(3)]);
+}
+
+#[test]
+fn hash_eq() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    set.insert(SharedVector::from_slice(&[1u32, 2, 3]));
+    assert!(set.contains(&SharedVector::from_slice(&[1u32, 2, 3])));
+    assert!(!set.contains(&SharedVector::from_slice(&[1u32, 2])));
+}
+
+#[test]
+fn convert_ref_count() {
+    let a: SharedVector<Box<u32>> = SharedVector::from_slice(&[num(1), num(2)]);
+    let a_ref = a.new_ref();
+    let b = AtomicSharedVector::from(a);
+    assert!(b.is_unique());
+    assert!(a_ref.is_unique());
+    assert_eq!(b.as_slice(), &[num(1), num(2)]);
+
+    let ptr = b.data_ptr();
+    let c = b.into_shared_vector();
+    assert_eq!(c.data_ptr(), ptr);
+    assert_eq!(c.as_slice(), a_ref.as_slice());
+
+    let d = std::thread::spawn(move || {
+        let d: AtomicSharedVector<u32> = crate::arc_vector![1, 2, 3];
+        d
+    }).join().unwrap();
+    let d2 = d.new_ref();
+    std::thread::spawn(move || assert_eq!(d2.as_slice(), &[1, 2, 3])).join().unwrap();
+}
+
+#[test]
+fn copy_on_write() {
+    let mut a: SharedVector<Box<u32>> = SharedVector::from_slice(&[num(1), num(2)]);
+    *a.get_mut().unwrap()[0] = 10;
+
+    let b = a.new_ref();
+    assert!(a.get_mut().is_none());
+
+    *a.make_unique()[1] = 20;
+    assert!(!a.ptr_eq(&b));
+    assert_eq!(a.as_slice(), &[num(10), num(20)]);
+    assert_eq!(b.as_slice(), &[num(10), num(2)]);
+    assert!(a.get_mut().is_some());
+}
+
+#[test]
+fn arc_slice() {
+    let a: SharedVector<Box<u32>> = SharedVector::from_slice(&[num(1), num(2)]);
+    let b = a.new_ref();
+    let arc: Arc<[Box<u32>]> = a.into();
+    assert_eq!(&arc[..], b.as_slice());
+
+    let arc2 = arc.clone();
+    let arc = SharedVector::try_from(arc).unwrap_err();
+    drop(arc2);
+
+    let c: SharedVector<Box<u32>> = SharedVector::try_from(arc).unwrap();
+    assert_eq!(c.as_slice(), &[num(1), num(2)]);
+
+    let arc: Arc<[Box<u32>]> = Arc::from(c);
+    let d = AtomicSharedVector::try_from(arc).unwrap();
+    assert_eq!(d.as_slice(), b.as_slice());
+}
+
+#[test]
+fn strong_count() {
+    let a: SharedVector<u32> = SharedVector::from_slice(&[1, 2, 3]);
+    assert_eq!(SharedVector::strong_count(&a), 1);
+    let b = a.new_ref();
+    assert_eq!(SharedVector::strong_count(&a), 2);
+    assert_eq!(SharedVector::strong_count(&b), 2);
+    drop(b);
+    assert_eq!(SharedVector::strong_count(&a), 1);
+
+    let c: AtomicSharedVector<u32> = AtomicSharedVector::new();
+    let d = c.clone();
+    assert_eq!(AtomicSharedVector::strong_count(&d), 2);
+    assert!(!c.is_unique());
+}
+
+#[test]
+fn shared_remove() {
+    let mut a = SharedVector::from_slice(&[Box::new(1u32), Box::new(2), Box::new(3), Box::new(4)]);
+    let b = a.new_ref();
+
+    assert_eq!(a.remove(1), Box::new(2));
+    assert_eq!(a.as_slice(), &[Box::new(1), Box::new(3), Box::new(4)]);
+    assert_eq!(b.as_slice(), &[Box::new(1), Box::new(2), Box::new(3), Box::new(4)]);
+
+    assert_eq!(a.swap_remove(0), Box::new(1));
+    assert_eq!(a.as_slice(), &[Box::new(4), Box::new(3)]);
+    assert_eq!(a.remove(1), Box::new(3));
+    assert_eq!(a.remove(0), Box::new(4));
+    assert!(a.is_empty());
+}
+
+#[test]
+fn drain_copy_on_write() {
+    let mut a: SharedVector<Box<u32>> = SharedVector::from(&[num(1), num(2), num(3), num(4)][..]);
+    let b = a.new_ref();
+
+    let drained: Vec<Box<u32>> = a.drain(1..3).collect();
+    assert_eq!(drained, vec![num(2), num(3)]);
+    assert_eq!(a.as_slice(), &[num(1), num(4)]);
+    assert_eq!(b.as_slice(), &[num(1), num(2), num(3), num(4)]);
+    assert!(a.is_unique());
+    assert!(b.is_unique());
+
+    // Draining a unique vector does not reallocate.
+    let ptr = a.data_ptr();
+    let mut drain = a.drain(..);
+    assert_eq!(drain.next(), Some(num(1)));
+    drop(drain);
+    assert!(a.is_empty());
+    assert_eq!(a.data_ptr(), ptr);
+
+    let mut c: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[1, 2, 3]);
+    let d = c.clone();
+    assert_eq!(c.drain(..1).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(c.as_slice(), &[2, 3]);
+    assert_eq!(d.as_slice(), &[1, 2, 3]);
+}
+
+#[test]
+fn concat() {
+    let a: SharedVector<Box<u32>> = SharedVector::from(&[num(1), num(2)][..]);
+    let b: SharedVector<Box<u32>> = SharedVector::from(&[num(3)][..]);
+    let c = SharedVector::concat(&a, &b);
+    assert_eq!(c.as_slice(), &[num(1), num(2), num(3)]);
+    assert_eq!(c.capacity(), 3);
+    assert_eq!(a.as_slice(), &[num(1), num(2)]);
+    assert_eq!(b.as_slice(), &[num(3)]);
+
+    let empty = SharedVector::new();
+    assert_eq!(SharedVector::concat(&empty, &b).as_slice(), &[num(3)]);
+
+    let a: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[1, 2, 3]);
+    let b: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[4, 5]);
+    let c = AtomicSharedVector::concat_copy(&a, &b);
+    assert_eq!(c.as_slice(), &[1, 2, 3, 4, 5]);
+    assert_eq!(c.capacity(), 5);
+    let empty: AtomicSharedVector<u32> = AtomicSharedVector::new();
+    assert!(AtomicSharedVector::concat_copy(&empty, &empty).is_empty());
+}
+
+#[test]
+fn into_iter() {
+    let a: SharedVector<Box<u32>> = SharedVector::from(&[num(1), num(2), num(3)][..]);
+    let b = a.new_ref();
+
+    let mut sum = 0;
+    for item in a {
+        sum += *item;
+    }
+    assert_eq!(sum, 6);
+    assert!(b.is_unique());
+
+    let mut iter = b.into_iter();
+    assert_eq!(iter.next_back(), Some(num(3)));
+    assert_eq!(iter.len(), 2);
+
+    let c: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[4, 5]);
+    assert_eq!((&c).into_iter().copied().collect::<Vec<_>>(), vec![4, 5]);
+    assert_eq!(c.into_iter().collect::<Vec<_>>(), vec![4, 5]);
+}
+
+#[test]
+fn freeze() {
+    let mut v = crate::vector![num(1), num(2)];
+    v.reserve(10);
+    let ptr = v.as_ptr();
+    let cap = v.capacity();
+
+    let a = SharedVector::freeze(v);
+    assert_eq!(a.as_slice(), &[num(1), num(2)]);
+    assert_eq!(a.as_ptr(), ptr);
+    assert_eq!(a.capacity(), cap);
+    assert!(a.is_unique());
+
+    let b = a.new_ref();
+    assert_eq!(SharedVector::strong_count(&a), 2);
+    drop(a);
+    let v = b.try_unique().unwrap();
+    assert_eq!(v.as_ptr(), ptr);
+
+    let c = AtomicSharedVector::freeze(v);
+    assert_eq!(c.as_slice(), &[num(1), num(2)]);
+
+    let empty: SharedVector<Box<u32>> = SharedVector::freeze(Vector::new());
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn thaw() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    // Not zero-sized, so the items have an address that must survive the conversions.
+    #[derive(Clone, Debug)]
+    struct Foo(#[allow(dead_code)] u32);
+    impl Drop for Foo {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let a: SharedVector<Foo> = SharedVector::freeze(crate::vector![Foo(1), Foo(2), Foo(3)]);
+    let b = a.new_ref();
+
+    let a = a.thaw().unwrap_err();
+    assert_eq!(SharedVector::strong_count(&a), 2);
+    drop(b);
+
+    let ptr = a.as_ptr();
+    let mut v = a.thaw().unwrap();
+    assert_eq!(v.as_ptr(), ptr);
+    assert_eq!(v.len(), 3);
+    v.push(Foo(4));
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+
+    drop(v);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn weak() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    #[derive(Clone, Debug)]
+    struct Foo;
+    impl Drop for Foo {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let a: SharedVector<Foo> = SharedVector::from_slice(&[Foo, Foo]);
+    DROPS.store(0, Ordering::Relaxed);
+    assert_eq!(SharedVector::weak_count(&a), 0);
+
+    let w = SharedVector::downgrade(&a);
+    let w2 = w.clone();
+    assert_eq!(SharedVector::weak_count(&a), 2);
+    assert_eq!(w.strong_count(), 1);
+    assert!(!a.is_unique());
+
+    let b = w.upgrade().unwrap();
+    assert!(b.ptr_eq(&a));
+    assert_eq!(SharedVector::strong_count(&a), 2);
+    drop(b);
+    drop(w2);
+
+    // Weak references prevent mutating in place.
+    let mut c = a.new_ref();
+    c.push(Foo);
+    assert!(!c.ptr_eq(&a));
+    assert!(c.is_unique());
+
+    drop(a);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+    assert!(w.upgrade().is_none());
+    assert_eq!(w.strong_count(), 0);
+    assert_eq!(w.weak_count(), 0);
+    drop(w);
+
+    drop(c);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 5);
+}
+
+#[test]
+fn atomic_weak() {
+    let a: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[1, 2, 3]);
+    let w = AtomicSharedVector::downgrade(&a);
+
+    let handle = std::thread::spawn(move || w.upgrade().map(|v| v.iter().sum::<u32>()));
+    let sum = handle.join().unwrap();
+    assert_eq!(sum, Some(6));
+    assert!(a.is_unique());
+
+    let w = AtomicSharedVector::downgrade(&a);
+    drop(a);
+    assert!(w.upgrade().is_none());
+}
+
+#[test]
+fn map() {
+    let a: SharedVector<Box<u32>> = SharedVector::from_slice(&[Box::new(1), Box::new(2), Box::new(3)]);
+    let b = a.new_ref();
+    let doubled = a.map(|x| Box::new(**x * 2));
+    assert_eq!(doubled.as_slice(), &[Box::new(2), Box::new(4), Box::new(6)]);
+    assert!(doubled.is_unique());
+    assert!(a.ptr_eq(&b));
+
+    let atomic: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[1, 2]);
+    assert_eq!(atomic.map(|x| x.to_string()).as_slice(), &["1".to_string(), "2".to_string()]);
+
+    let empty: SharedVector<u32> = SharedVector::new();
+    assert!(empty.map(|x| *x).is_empty());
+}
+
+#[test]
+fn filter_map() {
+    let a: SharedVector<Box<u32>> = SharedVector::from_slice(&[Box::new(1), Box::new(2), Box::new(3), Box::new(4)]);
+    let odd = a.filter_map(|x| if **x % 2 == 1 { Some(**x) } else { None });
+    assert_eq!(odd.as_slice(), &[1, 3]);
+    assert_eq!(odd.capacity(), 2);
+
+    let even = a.filter(|x| **x % 2 == 0);
+    assert_eq!(even.as_slice(), &[Box::new(2), Box::new(4)]);
+    assert_eq!(a.len(), 4);
+
+    assert!(a.filter(|_| false).is_empty());
+    assert_eq!(a.filter(|_| true).as_slice(), a.as_slice());
+}
+
+#[test]
+fn collect_shared() {
+    let a: SharedVector<Box<u32>> = SharedVector::from_slice(&[Box::new(1), Box::new(2)]);
+    let shared = a.new_ref();
+    let b: SharedVector<Box<u32>> = SharedVector::from_slice(&[Box::new(3)]);
+
+    let all: SharedVector<Box<u32>> = [a, SharedVector::new(), b].into_iter().collect();
+    assert_eq!(all.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+    assert_eq!(all.capacity(), 3);
+    assert_eq!(shared.len(), 2);
+
+    let mut v = crate::vector![Box::new(0u32)];
+    Extend::extend(&mut v, [shared, all]);
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(1), Box::new(2), Box::new(1), Box::new(2), Box::new(3)]);
+
+    let flat: AtomicSharedVector<u32> = (0..3u32).map(|i| AtomicSharedVector::from_slice(&[i, i])).collect();
+    assert_eq!(flat.as_slice(), &[0, 0, 1, 1, 2, 2]);
+}
+
+#[test]
+fn empty_singleton() {
+    let a: SharedVector<u32> = crate::empty();
+    let b = a.clone();
+    assert!(a.is_empty_singleton());
+    assert!(a.ptr_eq(&b));
+    assert!(a.is_empty());
+    assert!(!a.is_unique());
+    assert!(SharedVector::<String>::default().is_empty_singleton());
+    assert!(!SharedVector::<u32>::new().is_empty_singleton());
+
+    assert_eq!(SharedVector::strong_count(&a), 1);
+    assert_eq!(SharedVector::weak_count(&a), 0);
+
+    let w = SharedVector::downgrade(&a);
+    assert!(w.upgrade().unwrap().is_empty_singleton());
+    assert_eq!(w.strong_count(), 1);
+    drop(w.clone());
+
+    // Mutating allocates a buffer.
+    let mut c = b.clone();
+    c.push(1);
+    assert!(!c.is_empty_singleton());
+    assert!(c.is_unique());
+    assert_eq!(c.as_slice(), &[1]);
+    assert!(b.clone().into_unique().is_empty());
+
+    // Clearing and appending empty vectors keeps pointing to the singleton.
+    let mut e = b.clone();
+    e.clear();
+    assert!(e.is_empty_singleton());
+    e.append(&mut b.clone());
+    assert!(e.is_empty_singleton());
+    let mut f = b.clone();
+    c.append(&mut f);
+    assert!(f.is_empty_singleton());
+    assert_eq!(c.as_slice(), &[1]);
+
+    #[repr(align(256))]
+    struct Aligned;
+    let d: SharedVector<Aligned> = SharedVector::empty();
+    assert!(!d.is_empty_singleton());
+
+    let handles: Vec<_> = (0..4).map(|_| std::thread::spawn(|| {
+        let v: AtomicSharedVector<u64> = AtomicSharedVector::default();
+        let clones: Vec<_> = (0..100).map(|_| v.clone()).collect();
+        clones.iter().all(|c| c.is_empty_singleton())
+    })).collect();
+    for handle in handles {
+        assert!(handle.join().unwrap());
+    }
+}
@@ -0,0 +1,261 @@
+use core::fmt::Debug;
+use core::ops::{Deref, RangeBounds};
+
+use crate::alloc::{Allocator, Global};
+use crate::raw::BufferSize;
+use crate::vector::range_bounds;
+use crate::{DefaultRefCount, RefCount, RefCountedVector, Vector};
+
+/// A range of the items of a [`RefCountedVector`], sharing its buffer.
+///
+/// Created with [`RefCountedVector::slice`]. The reference count is stored at the start of the
+/// allocation, so a `SharedSlice` keeps the whole buffer alive, including the items outside of
+/// its range, and the buffer is not unique as long as the slice exists.
+///
+/// ```
+/// use shared_vector::SharedVector;
+/// let v = SharedVector::from_slice(&[1, 2, 3, 4, 5]);
+/// let s = v.slice(1..4);
+/// assert_eq!(s.as_slice(), &[2, 3, 4]);
+/// drop(v);
+/// assert_eq!(s.slice(1..).as_slice(), &[3, 4]);
+/// ```
+pub struct SharedSlice<T, R: RefCount = DefaultRefCount, A: Allocator = Global> {
+    buffer: RefCountedVector<T, R, A>,
+    offset: BufferSize,
+    len: BufferSize,
+}
+
+impl<T, R: RefCount, A: Allocator> SharedSlice<T, R, A> {
+    /// Creates a slice of the items of `vector` in `range`, taking over the reference to its buffer.
+    pub(crate) fn new<Range>(vector: RefCountedVector<T, R, A>, range: Range) -> Self
+    where
+        Range: RangeBounds<usize>,
+    {
+        let (start, end) = range_bounds(&range, vector.len());
+        SharedSlice {
+            buffer: vector,
+            offset: start as BufferSize,
+            len: (end - start) as BufferSize,
+        }
+    }
+
+    /// Returns the number of items in the slice.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns `true` if the slice contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data_ptr(), self.len()) }
+    }
+
+    /// Returns a pointer to the first item of the slice.
+    #[inline]
+    pub fn data_ptr(&self) -> *mut T {
+        unsafe { self.buffer.data_ptr().add(self.offset as usize) }
+    }
+
+    /// Returns a slice of the items of this slice in `range`, sharing the same buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the slice.
+    pub fn slice<Range>(&self, range: Range) -> Self
+    where
+        Range: RangeBounds<usize>,
+    {
+        let (start, end) = range_bounds(&range, self.len());
+        SharedSlice {
+            buffer: self.buffer.new_ref(),
+            offset: self.offset + start as BufferSize,
+            len: (end - start) as BufferSize,
+        }
+    }
+
+    /// Returns the vector whose buffer this slice points into.
+    #[inline]
+    pub fn buffer(&self) -> &RefCountedVector<T, R, A> {
+        &self.buffer
+    }
+
+    /// Returns true if the two slices point into the same buffer, whether or not their ranges overlap.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.buffer.ptr_eq(&other.buffer)
+    }
+
+    /// Returns true if the two slices cover the same items of the buffer.
+    pub fn data_ptr_eq(&self, other: &Self) -> bool {
+        self.data_ptr() == other.data_ptr() && self.len == other.len
+    }
+
+    /// Clones the items into a new unique vector.
+    pub fn to_vector(&self) -> Vector<T, A>
+    where
+        T: Clone,
+        A: Clone,
+    {
+        let mut vector = Vector::with_capacity_in(self.len(), self.buffer.allocator().clone());
+        vector.extend_from_slice(self.as_slice());
+        vector
+    }
+}
+
+/// Takes over the reference to the buffer, the slice contains all of the vector's items.
+impl<T, R: RefCount, A: Allocator> From<RefCountedVector<T, R, A>> for SharedSlice<T, R, A> {
+    fn from(vector: RefCountedVector<T, R, A>) -> Self {
+        SharedSlice::new(vector, ..)
+    }
+}
+
+impl<T, R: RefCount, A: Allocator> Clone for SharedSlice<T, R, A> {
+    fn clone(&self) -> Self {
+        SharedSlice {
+            buffer: self.buffer.new_ref(),
+            offset: self.offset,
+            len: self.len,
+        }
+    }
+}
+
+impl<T, R: RefCount, A: Allocator> Deref for SharedSlice<T, R, A> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, R: RefCount, A: Allocator> AsRef<[T]> for SharedSlice<T, R, A> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, R: RefCount, A: Allocator> PartialEq<SharedSlice<T, R, A>> for SharedSlice<T, R, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, R: RefCount, A: Allocator> PartialEq<&[T]> for SharedSlice<T, R, A> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: Eq, R: RefCount, A: Allocator> Eq for SharedSlice<T, R, A> {}
+
+impl<T: core::hash::Hash, R: RefCount, A: Allocator> core::hash::Hash for SharedSlice<T, R, A> {
+    fn hash<H>(&self, state: &mut H) where H: core::hash::Hasher {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: Debug, R: RefCount, A: Allocator> Debug for SharedSlice<T, R, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<'a, T, R: RefCount, A: Allocator> IntoIterator for &'a SharedSlice<T, R, A> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> core::slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+}
+
+#[cfg(test)]
+use crate::{AtomicSharedVector, SharedVector};
+
+#[test]
+fn shared_slice() {
+    let a = SharedVector::from_slice(&[Box::new(0u32), Box::new(1), Box::new(2), Box::new(3), Box::new(4)]);
+    let b = a.slice(1..4);
+    assert_eq!(b.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+    assert_eq!(b.len(), 3);
+    assert!(b.buffer().ptr_eq(&a));
+    assert_eq!(SharedVector::strong_count(&a), 2);
+    assert!(!a.is_unique());
+
+    // The slice keeps the buffer alive.
+    drop(a);
+    let c = b.slice(1..);
+    assert_eq!(c.as_slice(), &[Box::new(2), Box::new(3)]);
+    assert_eq!(b.slice(..), b);
+    assert!(c.ptr_eq(&b));
+    assert!(c.data_ptr_eq(&b.slice(1..)));
+    assert!(!c.data_ptr_eq(&b));
+    assert_eq!(c.to_vector().as_slice(), c.as_slice());
+
+    // Dropping the slices releases the buffer.
+    let d = b.buffer().clone();
+    drop(b);
+    drop(c);
+    assert!(d.is_unique());
+
+    let e = d.slice(2..2);
+    assert!(e.is_empty());
+    assert!(e.ptr_eq(&SharedSlice::from(d.new_ref())));
+    assert!(d.slice(5..).is_empty());
+    assert!(crate::empty::<u32>().slice(..).buffer().is_empty_singleton());
+
+    // Only the slices pay for the range.
+    assert_eq!(core::mem::size_of::<SharedVector<u32>>(), core::mem::size_of::<usize>());
+    assert_eq!(core::mem::size_of::<SharedSlice<u32>>(), core::mem::size_of::<usize>() + 8);
+
+    let f: AtomicSharedVector<u32> = AtomicSharedVector::from_slice(&[1, 2, 3]);
+    let g = f.slice(1..);
+    std::thread::spawn(move || assert_eq!(g.as_slice(), &[2, 3])).join().unwrap();
+}
+
+#[test]
+fn shared_slice_data_ptr_eq() {
+    let v = SharedVector::from_slice(&[1u32, 2, 3]);
+    let a = v.slice(0..2);
+    let b = v.slice(1..3);
+    assert!(a.ptr_eq(&b));
+    assert!(!a.data_ptr_eq(&b));
+    assert!(a.data_ptr_eq(&v.slice(..2)));
+    // Same start, different lengths.
+    assert!(!a.data_ptr_eq(&v.slice(..1)));
+
+    assert!(v.data_ptr_eq(&v.new_ref()));
+    assert!(!v.data_ptr_eq(&v.copy_buffer()));
+}
+
+#[test]
+fn shared_slice_alignment() {
+    #[repr(align(64))]
+    struct Aligned(u32);
+
+    let a: SharedVector<Aligned> = (0..5).map(Aligned).collect::<Vector<_>>().into_shared();
+    for start in 0..=5 {
+        let s = a.slice(start..);
+        assert_eq!(s.data_ptr() as usize % 64, 0);
+        assert_eq!(s.first().map(|x| x.0), a.get(start).map(|x| x.0));
+    }
+}
+
+#[test]
+#[should_panic]
+fn shared_slice_out_of_bounds() {
+    let a = SharedVector::from_slice(&[1u32, 2, 3]);
+    let _ = a.slice(1..2).slice(0..2);
+}
+
+#[test]
+#[should_panic]
+#[allow(clippy::reversed_empty_ranges)]
+fn shared_slice_inverted_range() {
+    let a = SharedVector::from_slice(&[1u32, 2, 3]);
+    let _ = a.slice(2..1);
+}
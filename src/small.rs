@@ -0,0 +1,600 @@
+use core::fmt::Debug;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::ptr::{self, NonNull};
+
+use crate::alloc::Global;
+use crate::drain::Drain;
+use crate::raw::{BufferSize, VecHeader};
+use crate::splice::Splice;
+use crate::vector::range_bounds;
+use crate::{RawVector, Vector};
+
+enum Storage<T, const N: usize> {
+    // Like in `ArrayVector`, `raw` describes the inline items so that `drain` and `splice` don't
+    // need to move them to the heap. `raw.data` is refreshed before use since the items move with the vector.
+    Inline { raw: RawVector<T>, items: [MaybeUninit<T>; N] },
+    Heap(Vector<T, Global>),
+}
+
+/// A vector that stores up to `N` elements inline and only allocates when it grows past `N`.
+///
+/// Once the items have moved to the heap, `SmallVector` behaves like a `Vector` and does not
+/// move them back inline, even if items are removed.
+///
+/// `SmallVector<T, 0>` never stores items inline and behaves like a `Vector<T>`.
+pub struct SmallVector<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+impl<T, const N: usize> SmallVector<T, N> {
+    /// Creates an empty vector.
+    ///
+    /// This does not allocate memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` exceeds `u32::MAX`.
+    pub fn new() -> Self {
+        if N == 0 {
+            return SmallVector { storage: Storage::Heap(Vector::new()) };
+        }
+
+        assert!(N <= BufferSize::MAX as usize);
+        SmallVector {
+            storage: Storage::Inline {
+                raw: RawVector {
+                    data: NonNull::dangling(),
+                    header: VecHeader { len: 0, cap: N as BufferSize },
+                },
+                items: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            },
+        }
+    }
+
+    /// Creates an empty vector with room for at least `cap` items.
+    ///
+    /// Allocates memory only if `cap` is greater than `N`.
+    pub fn with_capacity(cap: usize) -> Self {
+        if cap <= N {
+            return Self::new();
+        }
+
+        SmallVector { storage: Storage::Heap(Vector::with_capacity(cap)) }
+    }
+
+    /// Returns `true` if the items are stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline { .. })
+    }
+
+    /// Returns the number of items in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { raw, .. } => raw.header.len as usize,
+            Storage::Heap(vector) => vector.len(),
+        }
+    }
+
+    /// Returns `true` if the vector contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total number of items the vector can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => N,
+            Storage::Heap(vector) => vector.capacity(),
+        }
+    }
+
+    /// Returns true if the vector can't grow without allocating.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        match &self.storage {
+            Storage::Inline { raw, items } => unsafe {
+                core::slice::from_raw_parts(items.as_ptr() as *const T, raw.header.len as usize)
+            },
+            Storage::Heap(vector) => vector.as_slice(),
+        }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.storage {
+            Storage::Inline { raw, items } => unsafe {
+                core::slice::from_raw_parts_mut(items.as_mut_ptr() as *mut T, raw.header.len as usize)
+            },
+            Storage::Heap(vector) => vector.as_mut_slice(),
+        }
+    }
+
+    /// Moves the items to the heap if they don't fit inline with `additional` more items.
+    ///
+    /// Returns the heap storage if the items are on the heap after this call.
+    fn reserve_storage(&mut self, additional: usize) -> Option<&mut Vector<T, Global>> {
+        if self.is_inline() {
+            if self.len().saturating_add(additional) <= N {
+                return None;
+            }
+            self.spill(additional);
+        }
+
+        match &mut self.storage {
+            Storage::Heap(vector) => Some(vector),
+            Storage::Inline { .. } => None,
+        }
+    }
+
+    // Points `raw` at the inline items, which may have moved since the last call.
+    #[inline]
+    fn inline_raw<'l>(raw: &'l mut RawVector<T>, items: &mut [MaybeUninit<T>; N]) -> &'l mut RawVector<T> {
+        unsafe {
+            raw.data = NonNull::new_unchecked(items.as_mut_ptr() as *mut T);
+        }
+        raw
+    }
+
+    /// Moves the items to the heap with room for `additional` more items.
+    fn spill(&mut self, additional: usize) -> &mut Vector<T, Global> {
+        if let Storage::Inline { raw, items } = &mut self.storage {
+            let count = raw.header.len as usize;
+            let cap = crate::grow_amortized(count, additional).max(N * 2);
+            let mut vector = Vector::with_capacity(cap);
+            unsafe {
+                ptr::copy_nonoverlapping(items.as_ptr() as *const T, vector.as_mut_ptr(), count);
+                raw.header.len = 0;
+                vector.set_len(count);
+            }
+            self.storage = Storage::Heap(vector);
+        }
+
+        match &mut self.storage {
+            Storage::Heap(vector) => vector,
+            Storage::Inline { .. } => unreachable!(),
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more items.
+    ///
+    /// Moves the items to the heap if they would not fit inline.
+    pub fn reserve(&mut self, additional: usize) {
+        if let Some(vector) = self.reserve_storage(additional) {
+            vector.reserve(additional);
+        }
+    }
+
+    /// Appends an element to the back of the vector.
+    #[inline]
+    pub fn push(&mut self, val: T) {
+        match &mut self.storage {
+            Storage::Inline { raw, items } if (raw.header.len as usize) < N => {
+                items[raw.header.len as usize].write(val);
+                raw.header.len += 1;
+            }
+            Storage::Inline { .. } => {
+                self.spill(1).push(val);
+            }
+            Storage::Heap(vector) => {
+                vector.push(val);
+            }
+        }
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.storage {
+            Storage::Inline { raw, items } => {
+                if raw.header.len == 0 {
+                    return None;
+                }
+                raw.header.len -= 1;
+                unsafe { Some(items[raw.header.len as usize].assume_init_read()) }
+            }
+            Storage::Heap(vector) => vector.pop(),
+        }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all elements after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        let len = self.len();
+        assert!(index <= len, "insertion index (is {index}) should be <= len (is {len})");
+
+        if let Some(vector) = self.reserve_storage(1) {
+            vector.insert(index, element);
+            return;
+        }
+
+        if let Storage::Inline { raw, items } = &mut self.storage {
+            unsafe {
+                let ptr = (items.as_mut_ptr() as *mut T).add(index);
+                ptr::copy(ptr, ptr.add(1), len - index);
+                ptr::write(ptr, element);
+            }
+            raw.header.len += 1;
+        }
+    }
+
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        match &mut self.storage {
+            Storage::Inline { raw, items } => {
+                let len = raw.header.len as usize;
+                assert!(index < len, "removal index (is {index}) should be < len (is {len})");
+                unsafe {
+                    let ptr = (items.as_mut_ptr() as *mut T).add(index);
+                    let item = ptr::read(ptr);
+                    ptr::copy(ptr.add(1), ptr, len - index - 1);
+                    raw.header.len -= 1;
+                    item
+                }
+            }
+            Storage::Heap(vector) => vector.remove(index),
+        }
+    }
+
+    /// Removes an element from the vector and returns it.
+    ///
+    /// The removed element is replaced by the last element of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(index < len, "swap_remove index (is {index}) should be < len (is {len})");
+        self.as_mut_slice().swap(index, len - 1);
+        self.pop().unwrap()
+    }
+
+    /// Shortens the vector, keeping the first `new_len` elements and dropping the rest.
+    ///
+    /// If `new_len` is greater than or equal to the vector's current length, this has no effect.
+    pub fn truncate(&mut self, new_len: usize) {
+        match &mut self.storage {
+            Storage::Inline { raw, items } => Self::inline_raw(raw, items).truncate(new_len),
+            Storage::Heap(vector) => vector.truncate(new_len),
+        }
+    }
+
+    /// Clears the vector, removing all values.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Clones and appends the contents of the slice to the back of the vector.
+    pub fn extend_from_slice(&mut self, data: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(data.len());
+        for item in data {
+            self.push(item.clone());
+        }
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator.
+    ///
+    /// Inline items stay inline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        match &mut self.storage {
+            Storage::Inline { raw, items } => Self::inline_raw(raw, items).drain(range),
+            Storage::Heap(vector) => vector.drain(range),
+        }
+    }
+
+    /// Creates a splicing iterator that replaces the specified range in the vector
+    /// with the given `replace_with` iterator and yields the removed items.
+    ///
+    /// The replacement happens when the `Splice` is dropped, see [`Vector::splice`].
+    /// Inline items stay inline if the upper bound of `replace_with`'s size hint shows
+    /// that the result fits, otherwise they are moved to the heap first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn splice<R, I>(
+        &mut self,
+        range: R,
+        replace_with: I
+    ) -> Splice<'_, <I as IntoIterator>::IntoIter, Global>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        let replace_with = replace_with.into_iter();
+        if let Storage::Inline { raw, .. } = &self.storage {
+            let len = raw.header.len as usize;
+            let (start, end) = range_bounds(&range, len);
+            let kept = len - (end - start);
+            let (lower, upper) = replace_with.size_hint();
+            if !matches!(upper, Some(upper) if upper <= N - kept) {
+                self.spill(lower.saturating_sub(end - start));
+            }
+        }
+
+        match &mut self.storage {
+            Storage::Inline { raw, items } => {
+                // Derive both the drain's pointers and the splice's pointer from the same raw pointer.
+                let vec: *mut RawVector<T> = Self::inline_raw(raw, items);
+                unsafe {
+                    Splice {
+                        drain: Drain::new((*vec).data, NonNull::new_unchecked(ptr::addr_of_mut!((*vec).header)), range),
+                        vec: NonNull::new_unchecked(vec),
+                        replace_with,
+                        allocator: None,
+                    }
+                }
+            }
+            Storage::Heap(vector) => vector.splice(range, replace_with),
+        }
+    }
+
+    /// Converts this vector into a heap allocated `Vector`.
+    ///
+    /// Does not reallocate if the items are already on the heap.
+    pub fn into_vector(mut self) -> Vector<T, Global> {
+        let len = self.len();
+        if len == 0 && self.is_inline() {
+            return Vector::new();
+        }
+
+        let vector = self.spill(0);
+        mem::take(vector)
+    }
+}
+
+impl<T, const N: usize> Drop for SmallVector<T, N> {
+    fn drop(&mut self) {
+        if let Storage::Inline { .. } = self.storage {
+            unsafe {
+                ptr::drop_in_place(self.as_mut_slice());
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallVector<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallVector<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::with_capacity(self.len());
+        clone.extend_from_slice(self.as_slice());
+        clone
+    }
+}
+
+impl<T: PartialEq<T>, const N: usize> PartialEq<SmallVector<T, N>> for SmallVector<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, const N: usize> PartialEq<&[T]> for SmallVector<T, N> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallVector<T, N> {}
+
+impl<T, const N: usize> AsRef<[T]> for SmallVector<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for SmallVector<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> Deref for SmallVector<T, N> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallVector<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for SmallVector<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVector<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> core::slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallVector<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+    fn into_iter(self) -> core::slice::IterMut<'a, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallVector<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallVector<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = SmallVector::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+/// Takes over the heap allocation of the `Vector`.
+impl<T, const N: usize> From<Vector<T, Global>> for SmallVector<T, N> {
+    fn from(vector: Vector<T, Global>) -> Self {
+        SmallVector { storage: Storage::Heap(vector) }
+    }
+}
+
+impl<T, const N: usize> From<SmallVector<T, N>> for Vector<T, Global> {
+    fn from(vector: SmallVector<T, N>) -> Self {
+        vector.into_vector()
+    }
+}
+
+#[test]
+fn small_inline() {
+    let mut v: SmallVector<Box<u32>, 4> = SmallVector::new();
+    assert!(v.is_inline());
+    assert_eq!(v.capacity(), 4);
+
+    for i in 0..4 {
+        v.push(Box::new(i));
+    }
+    assert!(v.is_inline());
+    assert!(v.is_full());
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(1), Box::new(2), Box::new(3)][..]);
+
+    v.insert(1, Box::new(10));
+    assert!(!v.is_inline());
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(10), Box::new(1), Box::new(2), Box::new(3)][..]);
+
+    assert_eq!(v.pop(), Some(Box::new(3)));
+    assert_eq!(v.remove(1), Box::new(10));
+    assert_eq!(v.swap_remove(0), Box::new(0));
+    assert_eq!(v.as_slice(), &[Box::new(2), Box::new(1)][..]);
+    assert!(!v.is_inline());
+
+    let mut v: SmallVector<Box<u32>, 4> = (0..3).map(Box::new).collect();
+    assert!(v.is_inline());
+    assert_eq!(v.remove(0), Box::new(0));
+    v.insert(2, Box::new(5));
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(5)][..]);
+    let c = v.clone();
+    assert!(c.is_inline());
+    assert_eq!(c, v);
+    v.truncate(1);
+    assert_eq!(v.as_slice(), &[Box::new(1)][..]);
+    v.clear();
+    assert!(v.is_empty());
+    assert_eq!(v.pop(), None);
+
+    let v: SmallVector<(), 2> = std::iter::repeat_n((), 5).collect();
+    assert_eq!(v.len(), 5);
+}
+
+#[test]
+fn small_drain_splice() {
+    let mut v: SmallVector<u32, 8> = (0..6).collect();
+    assert!(v.is_inline());
+    let drained: Vec<u32> = v.drain(1..3).collect();
+    assert_eq!(drained, vec![1, 2]);
+    assert_eq!(v.as_slice(), &[0, 3, 4, 5][..]);
+    assert!(v.is_inline());
+
+    let mut v: SmallVector<u32, 8> = (0..4).collect();
+    let removed: Vec<u32> = v.splice(1..2, [10, 11, 12]).collect();
+    assert_eq!(removed, vec![1]);
+    assert_eq!(v.as_slice(), &[0, 10, 11, 12, 2, 3][..]);
+    assert!(v.is_inline());
+
+    // Fills the inline storage exactly.
+    v.splice(6.., [4, 5]);
+    assert_eq!(v.as_slice(), &[0, 10, 11, 12, 2, 3, 4, 5][..]);
+    assert!(v.is_inline());
+
+    // The replacement might not fit, so the items are moved to the heap.
+    let mut v: SmallVector<Box<u32>, 4> = (0..3).map(Box::new).collect();
+    v.splice(1..2, (10..13).map(Box::new));
+    assert!(!v.is_inline());
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(10), Box::new(11), Box::new(12), Box::new(2)][..]);
+
+    let mut v: SmallVector<Box<u32>, 4> = (0..3).map(Box::new).collect();
+    v.splice(1..3, (10..13).map(Box::new).filter(|x| **x % 2 == 0));
+    assert!(v.is_inline());
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(10), Box::new(12)][..]);
+
+    let mut v: SmallVector<u32, 4> = (0..4).collect();
+    v.splice(.., core::iter::from_fn(|| None));
+    assert!(!v.is_inline());
+    assert!(v.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Required allocation size is too large")]
+fn small_reserve_overflow() {
+    let mut v: SmallVector<u32, 4> = (0..2).collect();
+    v.reserve(usize::MAX);
+}
+
+#[test]
+fn small_vector_conversions() {
+    let v: SmallVector<u32, 4> = (0..3).collect();
+    let vector: Vector<u32> = v.into();
+    assert_eq!(vector.as_slice(), &[0, 1, 2]);
+
+    let v: SmallVector<u32, 4> = vector.into();
+    assert!(!v.is_inline());
+    assert_eq!(v.as_slice(), &[0, 1, 2][..]);
+
+    let vector = SmallVector::<u32, 4>::new().into_vector();
+    assert_eq!(vector.capacity(), 0);
+
+    // With N = 0, the items are always stored on the heap.
+    let mut v: SmallVector<u32, 0> = SmallVector::new();
+    assert!(!v.is_inline());
+    assert_eq!(v.capacity(), 0);
+    v.push(1);
+    v.push(2);
+    assert!(!v.is_inline());
+    assert_eq!(v.as_slice(), &[1, 2][..]);
+    assert_eq!(v.into_vector().as_slice(), &[1, 2]);
+}
@@ -1,8 +1,11 @@
 use crate::alloc::{Allocator, Global};
-use core::ptr::{self};
+use core::mem::ManuallyDrop;
+use core::ptr::{self, NonNull};
 use core::slice::{self};
 
+use crate::alloc_crate::vec::Vec;
 use crate::drain::Drain;
+use crate::RawVector;
 
 /// A splicing iterator for `Vec`.
 ///
@@ -23,8 +26,44 @@ pub struct Splice<
     A: Allocator + 'a = Global,
 > {
     pub(crate) drain: Drain<'a, I::Item>,
+    /// The source vector, needed to grow it.
+    pub(crate) vec: NonNull<RawVector<I::Item>>,
     pub(crate) replace_with: I,
-    pub(crate) allocator: &'a A,
+    /// The allocator used to grow the vector, or `None` if its capacity is fixed.
+    pub(crate) allocator: Option<&'a A>,
+}
+
+impl<'a, I: Iterator, A: Allocator> Splice<'a, I, A> {
+    /// Abandons the replacement and returns the underlying draining iterator.
+    ///
+    /// The `replace_with` iterator is exhausted and dropped without inserting any
+    /// of its elements into the vector. The returned `Drain` can be used to continue
+    /// draining the not-yet-yielded elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut vec = vector![0, 1, 2, 3];
+    /// let mut splice = vec.splice(1..3, [7, 8]);
+    /// assert_eq!(splice.next(), Some(1));
+    /// let mut drain = splice.into_drain();
+    /// assert_eq!(drain.next(), Some(2));
+    /// drop(drain);
+    /// assert_eq!(vec.as_slice(), &[0, 3]);
+    /// ```
+    pub fn into_drain(self) -> Drain<'a, I::Item> {
+        // Prevent `Splice::drop` from running, it would otherwise install the
+        // replacement elements and drop the drain a second time.
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let drain = ptr::read(&this.drain);
+            let mut replace_with = ptr::read(&this.replace_with);
+            replace_with.by_ref().for_each(drop);
+
+            drain
+        }
+    }
 }
 
 impl<I: Iterator, A: Allocator> Iterator for Splice<'_, I, A> {
@@ -45,7 +84,11 @@ impl<I: Iterator, A: Allocator> DoubleEndedIterator for Splice<'_, I, A> {
     }
 }
 
-impl<I: Iterator, A: Allocator> ExactSizeIterator for Splice<'_, I, A> {}
+impl<I: Iterator, A: Allocator> ExactSizeIterator for Splice<'_, I, A> {
+    fn len(&self) -> usize {
+        self.drain.iter.len()
+    }
+}
 
 impl<I: Iterator, A: Allocator> Drop for Splice<'_, I, A> {
     fn drop(&mut self) {
@@ -55,11 +98,19 @@ impl<I: Iterator, A: Allocator> Drop for Splice<'_, I, A> {
         // Which means we can replace the slice::Iter with pointers that won't point to deallocated
         // memory, so that Drain::drop is still allowed to call iter.len(), otherwise it would break
         // the ptr.sub_ptr contract.
-        self.drain.iter = (&[]).iter();
+        self.drain.iter = [].iter();
 
         unsafe {
             if self.drain.tail_len == 0 {
-                self.drain.vec.as_mut().extend(self.allocator, self.replace_with.by_ref());
+                let vec = self.vec.as_mut();
+                match self.allocator {
+                    Some(allocator) => vec.extend(allocator, self.replace_with.by_ref()),
+                    None => {
+                        vec.extend_within_capacity(&mut self.replace_with);
+                        assert!(self.replace_with.next().is_none(), "splice exceeds the capacity of the vector");
+                    }
+                }
+                self.drain.data = vec.data;
                 return;
             }
 
@@ -72,7 +123,7 @@ impl<I: Iterator, A: Allocator> Drop for Splice<'_, I, A> {
             // FIXME: Is the upper bound a better guess? Or something else?
             let (lower_bound, _upper_bound) = self.replace_with.size_hint();
             if lower_bound > 0 {
-                self.drain.move_tail(self.allocator, lower_bound);
+                self.drain.move_tail(self.vec.as_mut(), self.allocator, lower_bound);
                 if !self.drain.fill(&mut self.replace_with) {
                     return;
                 }
@@ -83,7 +134,7 @@ impl<I: Iterator, A: Allocator> Drop for Splice<'_, I, A> {
             let mut collected = self.replace_with.by_ref().collect::<Vec<I::Item>>().into_iter();
             // Now we have an exact count.
             if collected.len() > 0 {
-                self.drain.move_tail(self.allocator, collected.len());
+                self.drain.move_tail(self.vec.as_mut(), self.allocator, collected.len());
                 let filled = self.drain.fill(&mut collected);
                 debug_assert!(filled);
                 debug_assert_eq!(collected.len(), 0);
@@ -100,17 +151,17 @@ impl<T> Drain<'_, T> {
     /// Fill that range as much as possible with new elements from the `replace_with` iterator.
     /// Returns `true` if we filled the entire range. (`replace_with.next()` didn’t return `None`.)
     unsafe fn fill<I: Iterator<Item = T>>(&mut self, replace_with: &mut I) -> bool {
-        let vec = unsafe { self.vec.as_mut() };
-        let range_start = vec.header.len as usize;
-        let range_end = self.tail_start as usize;
+        let header = unsafe { self.header.as_mut() };
+        let range_start = header.len as usize;
+        let range_end = self.tail_start;
         let range_slice = unsafe {
-            slice::from_raw_parts_mut(vec.as_mut_ptr().add(range_start), range_end - range_start)
+            slice::from_raw_parts_mut(self.data.as_ptr().add(range_start), range_end - range_start)
         };
 
         for place in range_slice {
             if let Some(new_item) = replace_with.next() {
                 unsafe { ptr::write(place, new_item) };
-                vec.header.len += 1;
+                header.len += 1;
             } else {
                 return false;
             }
@@ -119,10 +170,16 @@ impl<T> Drain<'_, T> {
     }
 
     /// Makes room for inserting more elements before the tail.
-    unsafe fn move_tail<A: Allocator>(&mut self, allocator: &A, additional: usize) {
-        let vec = unsafe { self.vec.as_mut() };
+    ///
+    /// Without an allocator, panics if the vector does not have enough capacity.
+    unsafe fn move_tail<A: Allocator>(&mut self, vec: &mut RawVector<T>, allocator: Option<&A>, additional: usize) {
         let len = self.tail_start + self.tail_len;
-        unsafe { vec.try_reserve(allocator, len + additional).unwrap(); }
+        match allocator {
+            Some(allocator) => unsafe { vec.try_reserve(allocator, len + additional).unwrap(); }
+            None => assert!(len + additional <= vec.capacity(), "splice exceeds the capacity of the vector"),
+        }
+        // Growing may have moved the items.
+        self.data = vec.data;
 
         let new_tail_start = self.tail_start + additional;
         unsafe {
@@ -132,4 +189,36 @@ impl<T> Drain<'_, T> {
         }
         self.tail_start = new_tail_start;
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn splice_exact_size() {
+    let mut vec = crate::vector![1u32, 2, 3, 4, 5];
+    let mut splice = vec.splice(1..4, [10, 11]);
+    assert_eq!(splice.len(), 3);
+    assert_eq!(splice.next(), Some(2));
+    assert_eq!(splice.len(), 2);
+    assert_eq!(splice.next_back(), Some(4));
+    assert_eq!(splice.len(), 1);
+    assert_eq!(splice.next(), Some(3));
+    assert_eq!(splice.len(), 0);
+    drop(splice);
+    assert_eq!(vec.as_slice(), &[1, 10, 11, 5]);
+
+    // The length only depends on the drained range, not on the replacement.
+    let splice = vec.splice(1..3, (0..10).filter(|x| x % 2 == 0));
+    assert_eq!(splice.len(), 2);
+    drop(splice);
+    assert_eq!(vec.as_slice(), &[1, 0, 2, 4, 6, 8, 5]);
+}
+
+#[test]
+fn splice_into_drain() {
+    let mut vec = crate::vector![Box::new(1u32), Box::new(2), Box::new(3), Box::new(4)];
+    let mut splice = vec.splice(1..3, [Box::new(10), Box::new(11)]);
+    assert_eq!(splice.next(), Some(Box::new(2)));
+    let drain = splice.into_drain();
+    assert_eq!(drain.len(), 1);
+    drain.keep_rest();
+    assert_eq!(vec.as_slice(), &[Box::new(1), Box::new(3), Box::new(4)]);
+}
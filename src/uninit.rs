@@ -0,0 +1,177 @@
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::marker::PhantomData;
+use core::ops::Range;
+use core::ptr;
+
+use crate::alloc::{Allocator, Global};
+use crate::{RawVector, Vector};
+
+/// A vector of uninitialized slots, for algorithms that don't initialize the items in order.
+///
+/// The slots are written with [`write`](Self::write) in any order, then the whole vector is
+/// converted into a `Vector<T>` with [`assume_init`](Self::assume_init), without copying.
+///
+/// The vector does not track which slots have been written: this is up to the caller, who
+/// must make sure that all of them have been before calling `assume_init`. Dropping an
+/// `UninitVector` does not drop the items that have been written.
+///
+/// ```
+/// use shared_vector::UninitVector;
+/// let mut v = UninitVector::new(3);
+/// v.write(2, "c");
+/// v.write(0, "a");
+/// v.write(1, "b");
+/// // SAFETY: All three slots have been written.
+/// let v = unsafe { v.assume_init() };
+/// assert_eq!(v.as_slice(), &["a", "b", "c"]);
+/// ```
+pub struct UninitVector<T, A: Allocator = Global> {
+    inner: Vector<MaybeUninit<T>, A>,
+}
+
+impl<T> UninitVector<T, Global> {
+    /// Creates a vector of `len` uninitialized slots.
+    pub fn new(len: usize) -> Self {
+        Self::new_in(len, Global)
+    }
+}
+
+impl<T, A: Allocator> UninitVector<T, A> {
+    /// Creates a vector of `len` uninitialized slots allocated with the provided allocator.
+    pub fn new_in(len: usize, allocator: A) -> Self {
+        let mut inner = Vector::with_capacity_in(len, allocator);
+        unsafe {
+            // Uninitialized `MaybeUninit` items are valid.
+            inner.set_len(len);
+        }
+
+        UninitVector { inner }
+    }
+
+    /// Returns the number of slots in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the vector contains no slots.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[MaybeUninit<T>] {
+        self.inner.as_slice()
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self.inner.as_mut_slice()
+    }
+
+    /// Writes `value` into the slot at `index`.
+    ///
+    /// If the slot had already been written, the previous value is overwritten without being dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn write(&mut self, index: usize, value: T) {
+        self.inner[index].write(value);
+    }
+
+    /// Returns the underlying vector of uninitialized slots.
+    pub fn into_inner(self) -> Vector<MaybeUninit<T>, A> {
+        self.inner
+    }
+
+    /// Converts this into a vector of initialized items, without copying.
+    ///
+    /// # Safety
+    ///
+    /// Every slot of the vector must have been initialized, with [`write`](Self::write) or
+    /// through [`as_mut_slice`](Self::as_mut_slice). Calling this when some of the slots are
+    /// uninitialized causes immediate undefined behavior, see [`MaybeUninit::assume_init`].
+    pub unsafe fn assume_init(self) -> Vector<T, A> {
+        // `MaybeUninit<T>` has the same size and alignment as `T`, so the buffers have the same layout.
+        let inner = ManuallyDrop::new(self.inner);
+        Vector {
+            raw: RawVector {
+                data: inner.raw.data.cast(),
+                header: inner.raw.header.clone(),
+            },
+            allocator: ptr::read(&inner.allocator),
+            growth: PhantomData,
+        }
+    }
+
+    /// Moves the items in `range` into a new vector, and returns it along with the remaining slots.
+    ///
+    /// The remaining slots keep their order and the buffer of this vector.
+    ///
+    /// # Safety
+    ///
+    /// Every slot in `range` must have been initialized, see [`assume_init`](Self::assume_init).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub unsafe fn assume_init_range(mut self, range: Range<usize>) -> (Vector<T, A>, Vector<MaybeUninit<T>, A>)
+    where
+        A: Clone,
+    {
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.len());
+
+        let count = range.end - range.start;
+        let mut init = Vector::with_capacity_in(count, self.inner.allocator().clone());
+        ptr::copy_nonoverlapping(self.inner.as_ptr().add(range.start) as *const T, init.as_mut_ptr(), count);
+        init.set_len(count);
+
+        // The slots don't need to be dropped, this only closes the gap.
+        self.inner.delete(range);
+
+        (init, self.inner)
+    }
+}
+
+impl<T, A: Allocator> From<Vector<MaybeUninit<T>, A>> for UninitVector<T, A> {
+    fn from(inner: Vector<MaybeUninit<T>, A>) -> Self {
+        UninitVector { inner }
+    }
+}
+
+#[test]
+fn uninit_write() {
+    let mut v = UninitVector::new(4);
+    assert_eq!(v.len(), 4);
+    for i in [3, 1, 0, 2] {
+        v.write(i, Box::new(i as u32));
+    }
+
+    let v = unsafe { v.assume_init() };
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(1), Box::new(2), Box::new(3)]);
+
+    let v: UninitVector<Box<u32>> = UninitVector::new(0);
+    assert!(unsafe { v.assume_init() }.is_empty());
+}
+
+#[test]
+fn uninit_range() {
+    let mut v = UninitVector::new(5);
+    v.write(3, Box::new(3u32));
+    v.write(1, Box::new(1));
+    v.write(2, Box::new(2));
+
+    let (init, mut rest) = unsafe { v.assume_init_range(1..4) };
+    assert_eq!(init.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+    assert_eq!(rest.len(), 2);
+
+    rest[0].write(Box::new(0));
+    rest[1].write(Box::new(4));
+    let rest = unsafe { UninitVector::from(rest).assume_init() };
+    assert_eq!(rest.as_slice(), &[Box::new(0), Box::new(4)]);
+}
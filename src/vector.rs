@@ -1,11 +1,19 @@
 use core::fmt::Debug;
 use core::ops::{Deref, DerefMut, Index, IndexMut};
 use core::ptr::NonNull;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use core::{mem, ptr};
 use core::ops::RangeBounds;
+use crate::alloc_crate::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::borrow::{Borrow, BorrowMut};
 
 use crate::alloc::{AllocError, Allocator, Global};
 use crate::drain::Drain;
+use crate::extract_if::ExtractIf;
+use crate::growth::{DoublingGrowth, GrowthStrategy};
+use crate::into_iter::IntoIter;
+use crate::windows_exact::WindowsExact;
 use crate::raw::{
     self, buffer_layout, AtomicRefCount, BufferSize, Header, HeaderBuffer, RefCount, VecHeader, move_data,
 };
@@ -41,15 +49,23 @@ pub struct RawVector<T> {
 
 impl<T> RawVector<T> {
     /// Creates an empty, unallocated raw vector.
+    ///
+    /// Zero-sized items don't need storage, so the vector can hold `u32::MAX` of them
+    /// without ever allocating.
     pub fn new() -> Self {
-        RawVector { data: NonNull::dangling(), header: VecHeader { len: 0, cap: 0 } }
+        let cap = if mem::size_of::<T>() == 0 { BufferSize::MAX } else { 0 };
+        RawVector { data: NonNull::dangling(), header: VecHeader { len: 0, cap } }
     }
 
     /// Creates an empty pre-allocated vector with a given storage capacity.
     ///
-    /// Does not allocate memory if `cap` is zero.
+    /// Does not allocate memory if `cap` is zero or if the items are zero-sized.
     pub fn try_with_capacity<A: Allocator>(allocator: &A, cap: usize) -> Result<RawVector<T>, AllocError> {
-        if cap == 0 {
+        if mem::size_of::<T>() == 0 && cap > BufferSize::MAX as usize {
+            return Err(AllocError);
+        }
+
+        if cap == 0 || mem::size_of::<T>() == 0 {
             return Ok(RawVector::new());
         }
 
@@ -106,12 +122,12 @@ impl<T> RawVector<T> {
     ///
     /// The provided allocator must be the one this raw vector was created with.
     pub unsafe fn deallocate<A: Allocator>(&mut self, allocator: &A) {
-        if self.header.cap == 0 {
+        self.clear();
+
+        if !self.is_allocated() {
             return;
         }
 
-        self.clear();
-
         self.deallocate_buffer(allocator);
 
         self.data = NonNull::dangling();
@@ -144,7 +160,7 @@ impl<T> RawVector<T> {
     }
 
     #[inline]
-    fn data_ptr(&self) -> *mut T {
+    pub(crate) fn data_ptr(&self) -> *mut T {
         self.data.as_ptr()
     }
 
@@ -159,14 +175,67 @@ impl<T> RawVector<T> {
     }
 
     /// Clears the vector, removing all values.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the vector.
     pub fn clear(&mut self) {
         unsafe {
             raw::clear(self.data_ptr(), &mut self.header)
         }
     }
 
+    /// Returns the remaining spare capacity of the vector as a slice of `MaybeUninit<T>`.
+    ///
+    /// The returned slice can be used to fill the vector with data (e.g. by reading from a file)
+    /// before marking the data as initialized using the `set_len` method.
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                self.data_ptr().add(self.len()) as *mut MaybeUninit<T>,
+                self.remaining_capacity(),
+            )
+        }
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to `capacity()`.
+    /// - The elements at `old_len..new_len` must be initialized.
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.header.len = new_len as BufferSize;
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+    ///
+    /// If `len` is greater or equal to the vector’s current length, this has no effect.
+    /// Note that this method has no effect on the allocated capacity of the vector.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+
+        unsafe {
+            let remaining_len = self.len() - len;
+            let tail = ptr::slice_from_raw_parts_mut(self.data_ptr().add(len), remaining_len);
+            // Update the length first so that a panicking destructor can't cause
+            // elements to be dropped twice.
+            self.header.len = len as BufferSize;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Returns `true` if the vector owns a buffer, which is never the case for zero-sized items.
+    #[inline]
+    pub(crate) fn is_allocated(&self) -> bool {
+        mem::size_of::<T>() != 0 && self.header.cap != 0
+    }
+
     unsafe fn base_ptr<A: Allocator>(&self, _allocator: &A) -> NonNull<u8> {
-        debug_assert!(self.header.cap > 0);
+        debug_assert!(self.is_allocated());
         raw::header_from_data_ptr::<Header<DefaultRefCount, A>, T>(self.data).cast()
     }
 
@@ -188,6 +257,25 @@ impl<T> RawVector<T> {
         raw::push_assuming_capacity(self.data_ptr(), &mut self.header, val);
     }
 
+    /// Appends an element to the back of a collection, or returns the element if the
+    /// allocation fails.
+    ///
+    /// The vector is left unchanged if an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
+    #[inline]
+    pub unsafe fn try_push<A: Allocator>(&mut self, allocator: &A, val: T) -> Result<(), T> {
+        if self.header.len == self.header.cap && self.try_realloc_additional(allocator, 1).is_err() {
+            return Err(val);
+        }
+
+        raw::push_assuming_capacity(self.data_ptr(), &mut self.header, val);
+
+        Ok(())
+    }
+
     /// Appends an element if there is sufficient spare capacity, otherwise an error is returned
     /// with the element.
     ///
@@ -219,6 +307,9 @@ impl<T> RawVector<T> {
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     ///
+    /// This preserves the order of the remaining elements and runs in *O*(*n*).
+    /// Use [`swap_remove`](Self::swap_remove) if the order does not matter.
+    ///
     /// # Panics
     ///
     /// Panics if `index` is out of bounds.
@@ -257,13 +348,16 @@ impl<T> RawVector<T> {
     ///
     /// The removed element is replaced by the last element of the vector.
     ///
+    /// This does not preserve the order of the remaining elements, but runs in *O*(1).
+    /// Use [`remove`](Self::remove) to preserve the order.
+    ///
     /// # Panics
     ///
     /// Panics if index is out of bounds.
     #[inline]
     pub fn swap_remove(&mut self, idx: usize) -> T {
         let len = self.len();
-        assert!(idx < len);
+        assert!(idx < len, "swap_remove index (is {idx}) should be < len (is {len})");
 
         unsafe {
             let ptr = self.data_ptr().add(idx);
@@ -287,6 +381,10 @@ impl<T> RawVector<T> {
     /// # Panics
     ///
     /// Panics if `index > len`.
+    ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
     pub unsafe fn insert<A: Allocator>(&mut self, allocator: &A, index: usize, element: T) {
         #[cold]
         #[inline(never)]
@@ -323,6 +421,28 @@ impl<T> RawVector<T> {
         }
     }
 
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right, or returns the element if the allocation fails.
+    ///
+    /// The vector is left unchanged if an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
+    pub unsafe fn try_insert<A: Allocator>(&mut self, allocator: &A, index: usize, element: T) -> Result<(), T> {
+        if self.try_reserve(allocator, 1).is_err() {
+            return Err(element);
+        }
+
+        self.insert(allocator, index, element);
+
+        Ok(())
+    }
+
     /// Clones and appends the contents of the slice to the back of a collection.
     ///
     /// # Safety
@@ -338,15 +458,96 @@ impl<T> RawVector<T> {
         }
     }
 
-    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    /// Resizes the vector in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the vector is extended by the difference, with each
+    /// additional slot filled with `value`. If `new_len` is less than `len`, the vector is
+    /// simply truncated.
+    ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
+    pub unsafe fn resize<A: Allocator>(&mut self, allocator: &A, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+
+        let additional = new_len - len;
+        self.try_reserve(allocator, additional).unwrap();
+
+        let data = self.data_ptr();
+        for _ in 1..additional {
+            raw::push_assuming_capacity(data, &mut self.header, value.clone());
+        }
+        raw::push_assuming_capacity(data, &mut self.header, value);
+    }
+
+    /// Resizes the vector in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the vector is extended by the difference, with each
+    /// additional slot filled with the result of calling the closure `f`. If `new_len` is less
+    /// than `len`, the vector is simply truncated.
+    ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
+    pub unsafe fn resize_with<A: Allocator, F>(&mut self, allocator: &A, new_len: usize, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+
+        let additional = new_len - len;
+        self.try_reserve(allocator, additional).unwrap();
+
+        let data = self.data_ptr();
+        for _ in 0..additional {
+            raw::push_assuming_capacity(data, &mut self.header, f());
+        }
+    }
+
+    /// Clones the elements from range `src` and appends them to the end of the vector.
     ///
     /// # Safety
     ///
     /// The provided allocator must be the one this raw vector was created with.
-    pub unsafe fn append<A: Allocator>(&mut self, allocator: &A, other: &mut Self)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub unsafe fn extend_from_within<A: Allocator, R>(&mut self, allocator: &A, src: R)
     where
         T: Clone,
+        R: RangeBounds<usize>,
     {
+        let (start, end) = range_bounds(&src, self.len());
+        self.try_reserve(allocator, end - start).unwrap();
+
+        // The source elements are accessed by index after the reservation, so reallocating
+        // does not invalidate them.
+        let data = self.data_ptr();
+        for idx in start..end {
+            let item = (*data.add(idx)).clone();
+            raw::push_assuming_capacity(data, &mut self.header, item);
+        }
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
+    pub unsafe fn append<A: Allocator>(&mut self, allocator: &A, other: &mut Self) {
         if other.is_empty() {
             return;
         }
@@ -376,22 +577,26 @@ impl<T> RawVector<T> {
         }
     }
 
-    unsafe fn extend_within_capacity(&mut self, iter: &mut impl Iterator<Item = T>) {
+    pub(crate) unsafe fn extend_within_capacity(&mut self, iter: &mut impl Iterator<Item = T>) {
         let n = self.remaining_capacity() as BufferSize;
 
         let mut ptr = self.data_ptr().add(self.len());
         let mut count = 0;
 
         unsafe {
-            for item in iter {
-                if count == n {
+            // Check the capacity before pulling an item so that no item is lost when the
+            // iterator produces more than its size hint announced.
+            while count < n {
+                let Some(item) = iter.next() else {
                     break;
-                }
+                };
                 ptr::write(ptr, item);
                 ptr = ptr.add(1);
                 count += 1;
+                // Keep the length up to date so that the written items are dropped if the
+                // iterator panics.
+                self.header.len += 1;
             }
-            self.header.len += count;
         }
     }
 
@@ -439,8 +644,25 @@ impl<T> RawVector<T> {
         type R = DefaultRefCount;
 
         unsafe {
+            if mem::size_of::<T>() == 0 {
+                // Zero-sized items are never allocated, the capacity is only limited by the
+                // type used to store the length.
+                if new_cap > BufferSize::MAX as usize {
+                    return Err(AllocError);
+                }
+                self.header.cap = BufferSize::MAX;
+                return Ok(());
+            }
+
             if new_cap == 0 {
-                self.deallocate_buffer(allocator);
+                if self.header.cap != 0 {
+                    self.deallocate_buffer(allocator);
+                }
+                return Ok(());
+            }
+
+            if new_cap > BufferSize::MAX as usize {
+                return Err(AllocError);
             }
 
             let new_layout = buffer_layout::<Header<R, A>, T>(new_cap).unwrap();
@@ -462,7 +684,7 @@ impl<T> RawVector<T> {
 
             let new_data_ptr = crate::raw::data_ptr::<Header<R, A>, T>(new_alloc.cast());
             self.data = NonNull::new_unchecked(new_data_ptr);
-            self.header.cap = new_cap as u32;
+            self.header.cap = new_cap as BufferSize;
         }
 
         Ok(())
@@ -498,8 +720,7 @@ impl<T> RawVector<T> {
     ///
     /// Unlike `try_reserve`, this will not deliberately over-allocate to speculatively avoid frequent allocations.
     /// After calling `reserve_exact`, capacity will be greater than or equal to `self.len() + additional`.
-    /// This will also allocate if the vector is not unique.
-    /// Does nothing if the capacity is already sufficient and the vector is unique.
+    /// Does nothing if the capacity is already sufficient.
     ///
     /// Note that the allocator may give the collection more space than it requests. Therefore, capacity can not
     /// be relied upon to be precisely minimal. Prefer `try_reserve` if future insertions are expected.
@@ -519,6 +740,7 @@ impl<T> RawVector<T> {
     ///
     /// The capacity will remain at least as large as both the length and the supplied value.
     /// If the current capacity is less than the lower limit, this is a no-op.
+    /// If the reallocation fails, the vector is left unchanged.
     ///
     /// # Safety
     ///
@@ -530,7 +752,7 @@ impl<T> RawVector<T> {
             return;
         }
 
-        self.try_realloc_with_capacity(allocator, min_capacity).unwrap();
+        let _ = self.try_realloc_with_capacity(allocator, min_capacity);
     }
 
     /// Shrinks the capacity of the vector as much as possible.
@@ -565,42 +787,113 @@ impl<T> RawVector<T> {
     where
         R: RangeBounds<usize>,
     {
-        // Memory safety
-        //
-        // When the Drain is first created, it shortens the length of
-        // the source vector to make sure no uninitialized or moved-from elements
-        // are accessible at all if the Drain's destructor never gets to run.
-        //
-        // Drain will ptr::read out the values to remove.
-        // When finished, remaining tail of the vec is copied back to cover
-        // the hole, and the vector length is restored to the new length.
-        //
-        use core::ops::Bound::*;
+        let vec: *mut Self = self;
+        unsafe {
+            Drain::new((*vec).data, NonNull::new_unchecked(ptr::addr_of_mut!((*vec).header)), range)
+        }
+    }
+
+    /// Removes the specified range from the vector in place, dropping the removed elements.
+    ///
+    /// This is equivalent to `self.drain(range).for_each(drop)` without building the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn delete<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        // Moves the tail back into place, even if dropping one of the removed elements panics.
+        struct MoveTail<'a, T> {
+            vec: &'a mut RawVector<T>,
+            start: usize,
+            end: usize,
+            len: usize,
+        }
+
+        impl<'a, T> Drop for MoveTail<'a, T> {
+            fn drop(&mut self) {
+                unsafe {
+                    let data = self.vec.data_ptr();
+                    ptr::copy(data.add(self.end), data.add(self.start), self.len - self.end);
+                    self.vec.header.len = (self.len - (self.end - self.start)) as BufferSize;
+                }
+            }
+        }
+
         let len = self.len();
-        let end = match range.end_bound() {
-            Included(n) => *n + 1,
-            Excluded(n) => *n,
-            Unbounded => len
-        };
-        let start = match range.start_bound() {
-            Included(n) => *n,
-            Excluded(n) => *n+1,
-            Unbounded => 0
-        };
-        assert!(end <= len);
-        assert!(start <= end);
+        let (start, end) = range_bounds(&range, len);
+        if start == end {
+            return;
+        }
 
         unsafe {
-            // Set self.vec length's to start, to be safe in case Drain is leaked
-            self.header.len = start as u32;
-            let range_slice = core::slice::from_raw_parts(self.as_ptr().add(start), end - start);
-            Drain {
-                tail_start: end,
-                tail_len: len - end,
-                iter: range_slice.iter(),
-                vec: NonNull::from(self),
-            }
-        }        
+            // Update the length first so that a panicking destructor can't cause
+            // elements to be dropped twice.
+            self.header.len = start as BufferSize;
+            let guard = MoveTail { vec: self, start, end, len };
+            let removed = ptr::slice_from_raw_parts_mut(guard.vec.data_ptr().add(start), end - start);
+            ptr::drop_in_place(removed);
+        }
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element in the range should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed from the vector and yielded.
+    /// If the closure returns `false`, or panics, the element remains in the vector and will not be yielded.
+    ///
+    /// Only elements that fall in the provided range are considered for extraction, but any elements
+    /// after the range will still have to be moved if any element has been extracted.
+    ///
+    /// If the returned `ExtractIf` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    /// Use [`retain`](Self::retain) with a negated predicate if you do not need the returned iterator.
+    ///
+    /// Using this method is equivalent to the following code, but `extract_if` does not
+    /// need to shift the same element more than once:
+    ///
+    /// ```
+    /// # let some_predicate = |x: &mut i32| { *x % 2 == 1 };
+    /// # let mut vec = shared_vector::vector![0, 1, 2, 3, 4, 5, 6];
+    /// # let mut vec2 = vec.clone();
+    /// # let range = 1..5;
+    /// let mut i = range.start;
+    /// let end_items = vec.len() - range.end;
+    /// # let mut extracted = shared_vector::Vector::new();
+    ///
+    /// while i < vec.len() - end_items {
+    ///     if some_predicate(&mut vec[i]) {
+    ///         let val = vec.remove(i);
+    ///         // your code here
+    /// #       extracted.push(val);
+    ///     } else {
+    ///         i += 1;
+    ///     }
+    /// }
+    ///
+    /// # let extracted2: shared_vector::Vector<_> = vec2.extract_if(range, some_predicate).collect();
+    /// # assert_eq!(vec, vec2);
+    /// # assert_eq!(extracted, extracted2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+        R: RangeBounds<usize>,
+    {
+        let old_len = self.len();
+        let (start, end) = range_bounds(&range, old_len);
+
+        // Guard against the vector getting leaked (leak amplification).
+        self.header.len = 0;
+
+        ExtractIf { vec: self, idx: start, del: 0, end, old_len, pred: filter }
     }
 
     /// Creates a splicing iterator that replaces the specified range in the vector
@@ -627,6 +920,9 @@ impl<T> RawVector<T> {
     /// Panics if the starting point is greater than the end point or if
     /// the end point is greater than the length of the vector.
     ///
+    /// # Safety
+    ///
+    /// The provided allocator must be the one this raw vector was created with.
     pub unsafe fn splice<'l, A, R, I>(
         &'l mut self,
         allocator: &'l A,
@@ -638,10 +934,15 @@ impl<T> RawVector<T> {
         R: RangeBounds<usize>,
         I: IntoIterator<Item = T>,
     {
-        Splice {
-            drain: self.drain(range),
-            replace_with: replace_with.into_iter(),
-            allocator,
+        // Derive both the drain's pointers and the splice's pointer from the same raw pointer.
+        let vec: *mut Self = self;
+        unsafe {
+            Splice {
+                drain: Drain::new((*vec).data, NonNull::new_unchecked(ptr::addr_of_mut!((*vec).header)), range),
+                vec: NonNull::new_unchecked(vec),
+                replace_with: replace_with.into_iter(),
+                allocator: Some(allocator),
+            }
         }
     }
 
@@ -753,13 +1054,229 @@ impl<T> RawVector<T> {
         drop(g);
     }
 
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a newly allocated vector containing the elements in the range `[at, len)`.
+    /// After the call, the original vector will be left containing the elements `[0, at)`
+    /// with its previous capacity unchanged.
+    ///
+    /// The provided allocator does not need to be the one this raw vector was created with.
+    /// The returned raw vector is considered to be created with the provided allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off<A: Allocator>(&mut self, allocator: &A, at: usize) -> Self {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(at: usize, len: usize) -> ! {
+            panic!("`at` split index (is {at}) should be <= len (is {len})");
+        }
+
+        let len = self.len();
+        if at > len {
+            assert_failed(at, len);
+        }
+
+        let other_len = len - at;
+        let mut other = Self::try_with_capacity(allocator, other_len).unwrap();
+
+        unsafe {
+            self.header.len = at as BufferSize;
+            ptr::copy_nonoverlapping(self.data_ptr().add(at), other.data_ptr(), other_len);
+            other.header.len = other_len as BufferSize;
+        }
+
+        other
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    #[inline]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements in the vector that resolve to the same
+    /// key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    #[inline]
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements in the vector satisfying a given equality
+    /// relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the vector and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the slice, so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        // Check if we ever want to remove anything.
+        // This allows to use copy_non_overlapping in next cycle.
+        // And avoids any memory writes if we don't need to remove anything.
+        let mut first_duplicate_idx: usize = 1;
+        let start = self.as_mut_ptr();
+        while first_duplicate_idx != len {
+            let found_duplicate = unsafe {
+                // SAFETY: first_duplicate always in range [1..len)
+                // Note that we start iteration from 1 so we never overflow.
+                let prev = start.add(first_duplicate_idx.wrapping_sub(1));
+                let current = start.add(first_duplicate_idx);
+                // We explicitly say in docs that references are reversed.
+                same_bucket(&mut *current, &mut *prev)
+            };
+            if found_duplicate {
+                break;
+            }
+            first_duplicate_idx += 1;
+        }
+        // Don't need to remove anything.
+        // We cannot get bigger than len.
+        if first_duplicate_idx == len {
+            return;
+        }
+
+        /* INVARIANT: vec.len() > read > write > write-1 >= 0 */
+        struct FillGapOnDrop<'a, T> {
+            /* Offset of the element we want to check if it is duplicate */
+            read: usize,
+
+            /* Offset of the place where we want to place the non-duplicate
+             * when we find it. */
+            write: usize,
+
+            /* The Vec that would need correction if `same_bucket` panicked */
+            vec: &'a mut RawVector<T>,
+        }
+
+        impl<'a, T> Drop for FillGapOnDrop<'a, T> {
+            fn drop(&mut self) {
+                /* This code gets executed when `same_bucket` panics */
+
+                /* SAFETY: invariant guarantees that `read - write`
+                 * and `len - read` never overflow and that the copy is always
+                 * in-bounds. */
+                unsafe {
+                    let ptr = self.vec.as_mut_ptr();
+                    let len = self.vec.len();
+
+                    /* How many items were left when `same_bucket` panicked.
+                     * Basically vec[read..].len() */
+                    let items_left = len.wrapping_sub(self.read);
+
+                    /* Pointer to first item in vec[write..write+items_left] slice */
+                    let dropped_ptr = ptr.add(self.write);
+                    /* Pointer to first item in vec[read..] slice */
+                    let valid_ptr = ptr.add(self.read);
+
+                    /* Copy `vec[read..]` to `vec[write..write+items_left]`.
+                     * The slices can overlap, so `copy_nonoverlapping` cannot be used */
+                    ptr::copy(valid_ptr, dropped_ptr, items_left);
+
+                    /* How many items have been already dropped
+                     * Basically vec[read..write].len() */
+                    let dropped = self.read.wrapping_sub(self.write);
+
+                    self.vec.header.len = (len - dropped) as BufferSize;
+                }
+            }
+        }
+
+        /* Drop items while going through Vec, it should be more efficient than
+         * doing slice partition_dedup + truncate */
+
+        // Construct gap first and then drop item to avoid memory corruption if `T::drop` panics.
+        let mut gap = FillGapOnDrop { read: first_duplicate_idx + 1, write: first_duplicate_idx, vec: self };
+        unsafe {
+            // SAFETY: we checked that first_duplicate_idx in bounds before.
+            // If drop panics, `gap` would remove this item without drop.
+            ptr::drop_in_place(start.add(first_duplicate_idx));
+        }
+
+        /* SAFETY: Because of the invariant, read_ptr, prev_ptr and write_ptr
+         * are always in-bounds and read_ptr never aliases prev_ptr */
+        unsafe {
+            while gap.read < len {
+                let read_ptr = start.add(gap.read);
+                let prev_ptr = start.add(gap.write.wrapping_sub(1));
+
+                // We explicitly say in docs that references are reversed.
+                let found_duplicate = same_bucket(&mut *read_ptr, &mut *prev_ptr);
+                if found_duplicate {
+                    // Increase `gap.read` now since the drop may panic.
+                    gap.read += 1;
+                    /* We have found duplicate, drop it in-place */
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    let write_ptr = start.add(gap.write);
+
+                    /* read_ptr cannot be equal to write_ptr because at this point
+                     * we guaranteed to skip at least one element (before loop starts).
+                     */
+                    ptr::copy_nonoverlapping(read_ptr, write_ptr, 1);
+
+                    /* We have filled that place, so go further */
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+
+            /* Technically we could let `gap` clean up with its Drop, but
+             * when `same_bucket` is guaranteed to not panic, this bloats a little
+             * the codegen, so we just do it manually */
+            gap.vec.header.len = gap.write as BufferSize;
+            mem::forget(gap);
+        }
+    }
+
     /// Transfers ownership of this raw vector's contents to the one that is returned, and leaves
     /// this one empty and unallocated.
     pub fn take(&mut self) -> Self {
-        mem::replace(self, RawVector::new())
+        mem::take(self)
     }
 }
 
+// Returns the start and end of a range of indices, panicking if the range does not fit in `[0, len]`.
+pub(crate) fn range_bounds<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    use core::ops::Bound::*;
+    let end = match range.end_bound() {
+        Included(n) => *n + 1,
+        Excluded(n) => *n,
+        Unbounded => len
+    };
+    let start = match range.start_bound() {
+        Included(n) => *n,
+        Excluded(n) => *n+1,
+        Unbounded => 0
+    };
+    assert!(end <= len);
+    assert!(start <= end);
+
+    (start, end)
+}
+
 pub trait FromIteratorIn<U, A: Allocator>: Sized {
     fn from_iter_in<T>(alloc: &A, iter: T) -> Self
     where
@@ -901,9 +1418,31 @@ impl<T: core::hash::Hash> core::hash::Hash for RawVector<T> {
 /// the storage.
 ///
 /// Internally, `Vector` is built on top of `RawVector`.
-pub struct Vector<T, A: Allocator = Global> {
-    pub(crate) raw: RawVector<T>,
+///
+/// # Slice methods
+///
+/// `Vector` dereferences to a slice, so slice methods such as `windows`, `chunks`,
+/// `chunks_exact` and their mutable variants can be called on it directly:
+///
+/// ```
+/// use shared_vector::vector;
+/// let mut v = vector![1, 2, 3, 4, 5, 6, 7];
+///
+/// let sums: Vec<i32> = v.chunks(3).map(|chunk| chunk.iter().sum()).collect();
+/// assert_eq!(sums, vec![6, 15, 7]);
+///
+/// for pair in v.chunks_exact_mut(2) {
+///     pair.swap(0, 1);
+/// }
+/// assert_eq!(v, vec![2, 1, 4, 3, 6, 5, 7]);
+///
+/// let increasing = v.windows(2).filter(|w| w[0] < w[1]).count();
+/// assert_eq!(increasing, 3);
+/// ```
+pub struct Vector<T, A: Allocator = Global, G: GrowthStrategy = DoublingGrowth> {
+    pub(crate) raw: RawVector<T>,
     pub(crate) allocator: A,
+    pub(crate) growth: PhantomData<fn() -> G>,
 }
 
 impl<T> Vector<T, Global> {
@@ -914,6 +1453,7 @@ impl<T> Vector<T, Global> {
         Vector {
             raw: RawVector::new(),
             allocator: Global,
+            growth: PhantomData,
         }
     }
 
@@ -937,7 +1477,7 @@ impl<T> Vector<T, Global> {
     where
         T: Clone,
     {
-        Vector { raw: RawVector::try_from_slice(&Global, data).unwrap(), allocator: Global }
+        Vector { raw: RawVector::try_from_slice(&Global, data).unwrap(), allocator: Global, growth: PhantomData }
     }
 
     /// Creates a vector with `n` clones of `elem`.
@@ -945,11 +1485,44 @@ impl<T> Vector<T, Global> {
     where
         T: Clone,
     {
-        Vector { raw: RawVector::try_from_elem(&Global, elem, n).unwrap(), allocator: Global }
+        Vector { raw: RawVector::try_from_elem(&Global, elem, n).unwrap(), allocator: Global, growth: PhantomData }
+    }
+
+    /// Creates a vector from a pointer to its first item, a length and a capacity.
+    ///
+    /// # Safety
+    ///
+    /// `ptr`, `len` and `cap` must come from a previous call to
+    /// [`into_raw_parts`](Self::into_raw_parts) or [`FixedVector::into_raw_parts`](crate::FixedVector::into_raw_parts).
+    ///
+    /// Unlike `Vec`, the items are preceded in memory by space for a header that this crate
+    /// manages, so a pointer to a buffer allocated elsewhere (by `Vec` or by C code for example)
+    /// can't be used. The caller does not need to do anything about the header, but must not
+    /// free the buffer or move it to another allocation.
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize, cap: usize) -> Vector<T, Global> {
+        debug_assert!(len <= cap);
+        Vector {
+            raw: RawVector {
+                data: NonNull::new_unchecked(ptr),
+                header: VecHeader { len: len as BufferSize, cap: cap as BufferSize },
+            },
+            allocator: Global,
+            growth: PhantomData,
+        }
+    }
+
+    /// Decomposes the vector into a pointer to its first item, its length and its capacity.
+    ///
+    /// The items can be read and written through the pointer, for example by C code, but
+    /// the memory must be released by turning it back into a vector with
+    /// [`from_raw_parts`](Self::from_raw_parts).
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let this = mem::ManuallyDrop::new(self);
+        (this.raw.data_ptr(), this.len(), this.capacity())
     }
 }
 
-impl<T, A: Allocator> Vector<T, A> {
+impl<T, A: Allocator, G: GrowthStrategy> Vector<T, A, G> {
     /// Creates an empty vector without allocating memory.
     pub fn new_in(allocator: A) -> Self {
         Self::try_with_capacity_in(0, allocator).unwrap()
@@ -965,10 +1538,10 @@ impl<T, A: Allocator> Vector<T, A> {
     /// Creates an empty pre-allocated vector with a given storage capacity.
     ///
     /// Does not allocate memory if `cap` is zero.
-    pub fn try_with_capacity_in(cap: usize, allocator: A) -> Result<Vector<T, A>, AllocError> {
+    pub fn try_with_capacity_in(cap: usize, allocator: A) -> Result<Vector<T, A, G>, AllocError> {
         let raw = RawVector::try_with_capacity(&allocator, cap)?;
 
-        Ok(Vector { raw, allocator })
+        Ok(Vector { raw, allocator, growth: PhantomData })
     }
 
 
@@ -996,6 +1569,24 @@ impl<T, A: Allocator> Vector<T, A> {
         self.raw.remaining_capacity()
     }
 
+    /// Returns the number of bytes reserved in front of the items of each buffer,
+    /// including the padding that aligns the first item.
+    pub fn header_bytes() -> usize {
+        raw::header_size::<Header<DefaultRefCount, A>, T>()
+    }
+
+    /// Returns the size in bytes of the vector's allocation, including the header.
+    ///
+    /// This is the size requested from the allocator, which may have reserved more.
+    /// Returns zero if the vector has not allocated.
+    pub fn allocated_bytes(&self) -> usize {
+        if !self.raw.is_allocated() {
+            return 0;
+        }
+
+        buffer_layout::<Header<DefaultRefCount, A>, T>(self.capacity()).unwrap().size()
+    }
+
     /// Returns a reference to the underlying allocator.
     #[inline(always)]
     pub fn allocator(&self) -> &A {
@@ -1012,29 +1603,85 @@ impl<T, A: Allocator> Vector<T, A> {
         self.raw.as_mut_slice()
     }
 
+    /// Returns a raw pointer to the vector's buffer.
+    ///
+    /// Unlike the pointer of `as_slice`, it is valid for the whole capacity of the vector.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.raw.data_ptr()
+    }
+
+    /// Returns an unsafe mutable pointer to the vector's buffer.
+    ///
+    /// Unlike the pointer of `as_mut_slice`, it is valid for the whole capacity of the vector.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.raw.data_ptr()
+    }
+
     /// Clears the vector, removing all values.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the vector.
     pub fn clear(&mut self) {
         unsafe {
             raw::clear(self.raw.data_ptr(), &mut self.raw.header)
         }
     }
 
-    unsafe fn into_header_buffer<R>(mut self) -> HeaderBuffer<T, R, A>
+    /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+    ///
+    /// If `len` is greater or equal to the vector’s current length, this has no effect.
+    /// Note that this method has no effect on the allocated capacity of the vector.
+    #[inline(always)]
+    pub fn truncate(&mut self, len: usize) {
+        self.raw.truncate(len)
+    }
+
+    /// Returns the remaining spare capacity of the vector as a slice of `MaybeUninit<T>`.
+    ///
+    /// The returned slice can be used to fill the vector with data (e.g. by reading from a file)
+    /// before marking the data as initialized using the `set_len` method.
+    #[inline(always)]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self.raw.spare_capacity_mut()
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to `capacity()`.
+    /// - The elements at `old_len..new_len` must be initialized.
+    #[inline(always)]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        self.raw.set_len(new_len)
+    }
+
+    pub(crate) unsafe fn into_header_buffer<R>(self) -> HeaderBuffer<T, R, A>
     where
         R: RefCount,
     {
         debug_assert!(self.raw.header.cap != 0);
         unsafe {
-            let mut header = raw::header_from_data_ptr(self.raw.data);
+            let data = if self.raw.is_allocated() {
+                self.raw.data
+            } else {
+                // Vectors of zero-sized items don't allocate, the buffer only has to hold the
+                // header once the vector is shared.
+                let (base_ptr, _) = raw::allocate_header_buffer::<T, A>(1, &self.allocator).unwrap();
+                NonNull::new_unchecked(raw::data_ptr::<raw::Header<R, A>, T>(base_ptr.cast()))
+            };
+            let header = raw::header_from_data_ptr(data);
 
-            *header.as_mut() = raw::Header {
+            ptr::write(header.as_ptr(), raw::Header {
                 vec: VecHeader {
                     len: self.raw.header.len,
                     cap: self.raw.header.cap,
                 },
                 ref_count: R::new(1),
-                allocator: ptr::read(&mut self.allocator),
-            };
+                weak_count: R::new(1),
+                allocator: ptr::read(&self.allocator),
+            });
 
             mem::forget(self);
 
@@ -1085,16 +1732,89 @@ impl<T, A: Allocator> Vector<T, A> {
     /// Panics if the new capacity exceeds `u32::MAX` bytes.
     #[inline(always)]
     pub fn push(&mut self, val: T) {
+        self.try_grow(1).unwrap();
         unsafe {
             self.raw.push(&self.allocator, val);
         }
     }
 
+    /// Appends an element to the back of a collection and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `u32::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::Vector;
+    /// struct Node { parent: Option<usize>, children: Vec<usize> }
+    ///
+    /// let mut nodes = Vector::new();
+    /// nodes.push(Node { parent: None, children: Vec::new() });
+    /// for parent in 0..3 {
+    ///     let child = nodes.len();
+    ///     let node = nodes.push_and_get_ref(Node { parent: Some(parent), children: Vec::new() });
+    ///     node.children.reserve(2);
+    ///     nodes[parent].children.push(child);
+    /// }
+    /// assert_eq!(nodes[3].parent, Some(2));
+    /// assert_eq!(nodes[2].children, vec![3]);
+    /// ```
+    #[inline]
+    pub fn push_and_get_ref(&mut self, val: T) -> &mut T {
+        self.try_grow(1).unwrap();
+        unsafe {
+            let len = self.len();
+            let slot = self.as_mut_ptr().add(len);
+            ptr::write(slot, val);
+            self.set_len(len + 1);
+
+            &mut *slot
+        }
+    }
+
+    /// Appends an element to the back of a collection, or returns the element if the
+    /// allocation fails.
+    ///
+    /// The vector is left unchanged if an error is returned.
+    #[inline(always)]
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
+        if self.try_grow(1).is_err() {
+            return Err(val);
+        }
+        unsafe {
+            self.raw.try_push(&self.allocator, val)
+        }
+    }
+
     /// Appends an element if there is sufficient spare capacity, otherwise an error is returned
     /// with the element.
     ///
     /// Unlike push this method will not reallocate when there’s insufficient capacity.
     /// The caller should use reserve or try_reserve to ensure that there is enough capacity.
+    ///
+    /// # Examples
+    ///
+    /// A producer loop filling a pre-allocated buffer and flushing it when it is full,
+    /// without ever reallocating:
+    ///
+    /// ```
+    /// use shared_vector::Vector;
+    /// let mut buffer = Vector::with_capacity(16);
+    /// let capacity = buffer.capacity();
+    /// let mut flushed = 0;
+    /// for i in 0..100u32 {
+    ///     if let Err(val) = buffer.push_within_capacity(i) {
+    ///         flushed += buffer.len();
+    ///         buffer.clear();
+    ///         buffer.push_within_capacity(val).unwrap();
+    ///     }
+    /// }
+    /// flushed += buffer.len();
+    /// assert_eq!(flushed, 100);
+    /// assert_eq!(buffer.capacity(), capacity);
+    /// ```
     #[inline(always)]
     pub fn push_within_capacity(&mut self, val: T) -> Result<(), T> {
         self.raw.push_within_capacity(val)
@@ -1106,9 +1826,38 @@ impl<T, A: Allocator> Vector<T, A> {
         self.raw.pop()
     }
 
+    /// Removes the first element from the vector and returns it, or `None` if it is empty.
+    ///
+    /// All other elements are shifted to the left, so this runs in *O*(*n*).
+    /// Consider a `VecDeque` if elements are frequently removed from the front of large vectors.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.remove(0))
+    }
+
+    /// Inserts an element at the front of the vector.
+    ///
+    /// All other elements are shifted to the right, so this runs in *O*(*n*).
+    /// Consider a `VecDeque` if elements are frequently added to the front of large vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `u32::MAX` bytes.
+    #[inline]
+    pub fn push_front(&mut self, val: T) {
+        self.insert(0, val);
+    }
+
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     ///
+    /// This preserves the order of the remaining elements and runs in *O*(*n*).
+    /// Use [`swap_remove`](Self::swap_remove) if the order does not matter.
+    ///
     /// # Panics
     ///
     /// Panics if `index` is out of bounds.
@@ -1118,10 +1867,37 @@ impl<T, A: Allocator> Vector<T, A> {
         self.raw.remove(index)
     }
 
+    /// Removes the first element equal to `value` and returns it, shifting all elements
+    /// after it to the left.
+    ///
+    /// Returns `None` if no element is equal to `value`.
+    pub fn remove_item(&mut self, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let index = self.iter().position(|item| item == value)?;
+        Some(self.remove(index))
+    }
+
+    /// Removes the last element equal to `value` and returns it, shifting all elements
+    /// after it to the left.
+    ///
+    /// Returns `None` if no element is equal to `value`.
+    pub fn remove_item_back(&mut self, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let index = self.iter().rposition(|item| item == value)?;
+        Some(self.remove(index))
+    }
+
     /// Removes an element from the vector and returns it.
     ///
     /// The removed element is replaced by the last element of the vector.
     ///
+    /// This does not preserve the order of the remaining elements, but runs in *O*(1).
+    /// Use [`remove`](Self::remove) to preserve the order.
+    ///
     /// # Panics
     ///
     /// Panics if index is out of bounds.
@@ -1138,7 +1914,24 @@ impl<T, A: Allocator> Vector<T, A> {
     /// Panics if `index > len`.
     #[inline(always)]
     pub fn insert(&mut self, index: usize, element: T) {
-       unsafe { self.raw.insert(&self.allocator, index, element) }
+        self.try_grow(1).unwrap();
+        unsafe { self.raw.insert(&self.allocator, index, element) }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right, or returns the element if the allocation fails.
+    ///
+    /// The vector is left unchanged if an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    #[inline(always)]
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), T> {
+        if self.try_grow(1).is_err() {
+            return Err(element);
+        }
+        unsafe { self.raw.try_insert(&self.allocator, index, element) }
     }
 
     /// Clones and appends the contents of the slice to the back of a collection.
@@ -1147,17 +1940,67 @@ impl<T, A: Allocator> Vector<T, A> {
     where
         T: Clone,
     {
+        self.try_grow(data.len()).unwrap();
         unsafe {
             self.raw.extend_from_slice(&self.allocator, data)
         }
     }
 
-    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    /// Resizes the vector in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the vector is extended by the difference, with each
+    /// additional slot filled with `value`. If `new_len` is less than `len`, the vector is
+    /// simply truncated.
+    #[inline(always)]
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.try_grow(new_len.saturating_sub(self.len())).unwrap();
+        unsafe {
+            self.raw.resize(&self.allocator, new_len, value)
+        }
+    }
+
+    /// Resizes the vector in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the vector is extended by the difference, with each
+    /// additional slot filled with the result of calling the closure `f`. If `new_len` is less
+    /// than `len`, the vector is simply truncated.
+    #[inline(always)]
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.try_grow(new_len.saturating_sub(self.len())).unwrap();
+        unsafe {
+            self.raw.resize_with(&self.allocator, new_len, f)
+        }
+    }
+
+    /// Clones the elements from range `src` and appends them to the end of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
     #[inline(always)]
-    pub fn append(&mut self, other: &mut Self)
+    pub fn extend_from_within<R>(&mut self, src: R)
     where
         T: Clone,
+        R: RangeBounds<usize>,
     {
+        let (start, end) = range_bounds(&src, self.len());
+        self.try_grow(end.saturating_sub(start)).unwrap();
+        unsafe {
+            self.raw.extend_from_within(&self.allocator, src)
+        }
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    #[inline(always)]
+    pub fn append(&mut self, other: &mut Self) {
+        self.try_grow(other.len()).unwrap();
         unsafe {
             self.raw.append(&self.allocator, &mut other.raw)
         }
@@ -1166,9 +2009,100 @@ impl<T, A: Allocator> Vector<T, A> {
     /// Appends the contents of an iterator to the back of a collection.
     #[inline(always)]
     pub fn extend(&mut self, data: impl IntoIterator<Item = T>) {
+        let mut iter = data.into_iter();
+        let (min, max) = iter.size_hint();
+        self.try_grow(max.unwrap_or(min)).unwrap();
+        unsafe {
+            self.raw.extend_within_capacity(&mut iter);
+        }
+        for item in iter {
+            self.push(item);
+        }
+    }
+
+    /// Reserves room for `max` items and appends up to `max` items from the iterator,
+    /// returning how many were appended.
+    ///
+    /// The iterator is not polled again once `max` items have been appended, so it can be
+    /// passed by mutable reference to fill fixed-size batches.
+    ///
+    /// ```
+    /// use shared_vector::Vector;
+    /// let (tx, rx) = std::sync::mpsc::channel();
+    /// for i in 0..5 {
+    ///     tx.send(i).unwrap();
+    /// }
+    ///
+    /// let mut batch = Vector::new();
+    /// assert_eq!(batch.push_from_iter(rx.try_iter(), 3), 3);
+    /// assert_eq!(batch, vec![0, 1, 2]);
+    /// assert_eq!(batch.push_from_iter(rx.try_iter(), 3), 2);
+    /// assert_eq!(batch, vec![0, 1, 2, 3, 4]);
+    /// ```
+    pub fn push_from_iter(&mut self, data: impl IntoIterator<Item = T>, max: usize) -> usize {
+        self.reserve(max);
+
+        let mut count = 0;
+        for item in data.into_iter().take(max) {
+            unsafe {
+                let len = self.len();
+                ptr::write(self.as_mut_ptr().add(len), item);
+                self.set_len(len + 1);
+            }
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Appends all of the items of an exactly sized iterator, reserving space for them up front.
+    ///
+    /// Unlike [`extend`](Self::extend), this allocates at most once and writes the items without
+    /// checking the capacity for each of them.
+    ///
+    /// ```
+    /// use shared_vector::Vector;
+    /// let mut v = Vector::new();
+    /// v.batch_push(vec![1, 2, 3]);
+    /// v.batch_push([4, 5].iter().copied());
+    /// assert_eq!(v, vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn batch_push<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        // Writes the length back when dropped, so that the pushed items are not leaked if
+        // the iterator panics.
+        struct SetLenOnDrop<'a> {
+            len: &'a mut BufferSize,
+            local_len: BufferSize,
+        }
+
+        impl<'a> Drop for SetLenOnDrop<'a> {
+            fn drop(&mut self) {
+                *self.len = self.local_len;
+            }
+        }
+
+        let mut iter = values.into_iter();
+        let count = iter.len();
+        self.reserve(count);
+
         unsafe {
-            self.raw.extend(&self.allocator, data)
+            let data = self.as_mut_ptr();
+            let header = &mut self.raw.header;
+            let mut len = SetLenOnDrop { local_len: header.len, len: &mut header.len };
+            // Safe code can implement `ExactSizeIterator` incorrectly, so the number of
+            // items written is bounded by what was reserved.
+            for item in iter.by_ref().take(count) {
+                ptr::write(data.add(len.local_len as usize), item);
+                len.local_len += 1;
+            }
         }
+
+        // In case the iterator yields more items than it announced.
+        self.extend(iter);
     }
 
     /// Allocates a clone of this buffer.
@@ -1181,6 +2115,7 @@ impl<T, A: Allocator> Vector<T, A> {
         Vector {
             raw: self.raw.clone_buffer(&self.allocator),
             allocator: self.allocator.clone(),
+            growth: PhantomData,
         }
     }
 
@@ -1196,20 +2131,41 @@ impl<T, A: Allocator> Vector<T, A> {
         Vector {
             raw: self.raw.clone_buffer_with_capacity(&self.allocator, cap),
             allocator: self.allocator.clone(),
+            growth: PhantomData,
         }
     }
 
     #[inline(always)]
     pub fn reserve(&mut self, additional: usize) {
-        unsafe {
-            self.raw.try_reserve(&self.allocator, additional).unwrap()
-        }
+        self.try_grow(additional).unwrap()
     }
 
     #[inline(always)]
     pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.try_grow(additional)
+    }
+
+    /// Makes room for `additional` items, growing the buffer according to `G`.
+    #[inline(always)]
+    fn try_grow(&mut self, additional: usize) -> Result<(), AllocError> {
+        if self.remaining_capacity() >= additional {
+            return Ok(());
+        }
+
+        self.try_grow_cold(additional)
+    }
+
+    #[cold]
+    fn try_grow_cold(&mut self, additional: usize) -> Result<(), AllocError> {
+        let len = self.len();
+        let required = len.checked_add(additional).ok_or(AllocError)?;
+        let new_cap = G::next_capacity(len, required);
+        if new_cap < required {
+            return Err(AllocError);
+        }
+
         unsafe {
-            self.raw.try_reserve(&self.allocator, additional)
+            self.raw.try_realloc_with_capacity(&self.allocator, new_cap)
         }
     }
 
@@ -1218,8 +2174,7 @@ impl<T, A: Allocator> Vector<T, A> {
     /// Unlike `reserve`, this will not deliberately over-allocate to speculatively avoid frequent allocations.
     /// After calling `try_reserve_exact`, capacity will be greater than or equal to `self.len() + additional` if
     /// it returns `Ok(())`.
-    /// This will also allocate if the vector is not unique.
-    /// Does nothing if the capacity is already sufficient and the vector is unique.
+    /// Does nothing if the capacity is already sufficient.
     ///
     /// Note that the allocator may give the collection more space than it requests. Therefore, capacity can not
     /// be relied upon to be precisely minimal. Prefer `try_reserve` if future insertions are expected.
@@ -1234,8 +2189,7 @@ impl<T, A: Allocator> Vector<T, A> {
     ///
     /// Unlike `try_reserve`, this will not deliberately over-allocate to speculatively avoid frequent allocations.
     /// After calling `reserve_exact`, capacity will be greater than or equal to `self.len() + additional`.
-    /// This will also allocate if the vector is not unique.
-    /// Does nothing if the capacity is already sufficient and the vector is unique.
+    /// Does nothing if the capacity is already sufficient.
     ///
     /// Note that the allocator may give the collection more space than it requests. Therefore, capacity can not
     /// be relied upon to be precisely minimal. Prefer `try_reserve` if future insertions are expected.
@@ -1249,11 +2203,9 @@ impl<T, A: Allocator> Vector<T, A> {
     ///
     /// The capacity will remain at least as large as both the length and the supplied value.
     /// If the current capacity is less than the lower limit, this is a no-op.
+    /// If the reallocation fails, the vector is left unchanged.
     #[inline(always)]
-    pub fn shrink_to(&mut self, min_capacity: usize)
-    where
-        T: Clone,
-    {
+    pub fn shrink_to(&mut self, min_capacity: usize) {
         unsafe {
             self.raw.shrink_to(&self.allocator, min_capacity)
         }
@@ -1261,10 +2213,7 @@ impl<T, A: Allocator> Vector<T, A> {
 
     /// Shrinks the capacity of the vector as much as possible.
     #[inline(always)]
-    pub fn shrink_to_fit(&mut self)
-    where
-        T: Clone,
-    {
+    pub fn shrink_to_fit(&mut self) {
         unsafe {
             self.raw.shrink_to_fit(&self.allocator)
         }
@@ -1288,6 +2237,7 @@ impl<T, A: Allocator> Vector<T, A> {
     /// [`mem::forget`], for example), the vector may have lost and leaked
     /// elements arbitrarily, including elements outside the range.
     ///
+    /// Use [`drain_all`](Self::drain_all) to remove all elements.
     pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
     where
         R: RangeBounds<usize>,
@@ -1295,6 +2245,99 @@ impl<T, A: Allocator> Vector<T, A> {
         self.raw.drain(range)
     }
 
+    /// Removes all elements from the vector, returning them as an iterator.
+    ///
+    /// This is equivalent to [`drain(..)`](Self::drain). The capacity of the vector is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::{vector, Vector};
+    /// let mut src = vector![1, 2, 3];
+    /// let mut dst = Vector::new();
+    /// dst.extend(src.drain_all());
+    /// assert!(src.is_empty());
+    /// assert_eq!(dst.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn drain_all(&mut self) -> Drain<'_, T> {
+        self.drain(..)
+    }
+
+    /// Removes the specified range from the vector in place, dropping the removed elements.
+    ///
+    /// This is equivalent to `self.drain(range).for_each(drop)` without building the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let mut v = vector![1, 2, 3, 4, 5];
+    /// v.delete(1..3);
+    /// assert_eq!(v.as_slice(), &[1, 4, 5]);
+    /// ```
+    pub fn delete<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.raw.delete(range)
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element in the range should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed from the vector and yielded.
+    /// If the closure returns `false`, or panics, the element remains in the vector and will not be yielded.
+    ///
+    /// Only elements that fall in the provided range are considered for extraction, but any elements
+    /// after the range will still have to be moved if any element has been extracted.
+    ///
+    /// If the returned `ExtractIf` is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    /// Use [`retain`](Self::retain) with a negated predicate if you do not need the returned iterator.
+    ///
+    /// Using this method is equivalent to the following code, but `extract_if` does not
+    /// need to shift the same element more than once:
+    ///
+    /// ```
+    /// # let some_predicate = |x: &mut i32| { *x % 2 == 1 };
+    /// # let mut vec = shared_vector::vector![0, 1, 2, 3, 4, 5, 6];
+    /// # let mut vec2 = vec.clone();
+    /// # let range = 1..5;
+    /// let mut i = range.start;
+    /// let end_items = vec.len() - range.end;
+    /// # let mut extracted = shared_vector::Vector::new();
+    ///
+    /// while i < vec.len() - end_items {
+    ///     if some_predicate(&mut vec[i]) {
+    ///         let val = vec.remove(i);
+    ///         // your code here
+    /// #       extracted.push(val);
+    ///     } else {
+    ///         i += 1;
+    ///     }
+    /// }
+    ///
+    /// # let extracted2: shared_vector::Vector<_> = vec2.extract_if(range, some_predicate).collect();
+    /// # assert_eq!(vec, vec2);
+    /// # assert_eq!(extracted, extracted2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+        R: RangeBounds<usize>,
+    {
+        self.raw.extract_if(range, filter)
+    }
+
     /// Creates a splicing iterator that replaces the specified range in the vector
     /// with the given `replace_with` iterator and yields the removed items.
     /// `replace_with` does not need to be the same length as `range`.
@@ -1355,76 +2398,462 @@ impl<T, A: Allocator> Vector<T, A> {
         self.raw.retain_mut(f)
     }
 
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a newly allocated vector containing the elements in the range `[at, len)`.
+    /// After the call, the original vector will be left containing the elements `[0, at)`
+    /// with its previous capacity unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
     #[inline(always)]
-    pub fn take(&mut self) -> Self
+    pub fn split_off(&mut self, at: usize) -> Self
     where
         A: Clone,
     {
-        let other = Vector {
-            raw: self.raw.take(),
+        Vector {
+            raw: self.raw.split_off(&self.allocator, at),
             allocator: self.allocator.clone(),
-        };
-
-        other
-    }
-}
-
-impl<T, A: Allocator> Drop for Vector<T, A> {
-    fn drop(&mut self) {
-        unsafe {
-            self.raw.deallocate(&self.allocator)
+            growth: PhantomData,
         }
     }
-}
 
-impl<T: Clone, A: Allocator + Clone> Clone for Vector<T, A> {
-    fn clone(&self) -> Self {
-        self.clone_buffer()
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    #[inline(always)]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.raw.dedup()
     }
-}
 
-impl<T: PartialEq<T>, A: Allocator> PartialEq<Vector<T, A>> for Vector<T, A> {
-    fn eq(&self, other: &Self) -> bool {
+    /// Removes all but the first of consecutive elements in the vector that resolve to the same
+    /// key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    #[inline(always)]
+    pub fn dedup_by_key<F, K>(&mut self, key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.raw.dedup_by_key(key)
+    }
+
+    /// Removes all but the first of consecutive elements in the vector satisfying a given equality
+    /// relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the vector and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the slice, so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    #[inline(always)]
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        self.raw.dedup_by(same_bucket)
+    }
+
+    #[inline(always)]
+    pub fn take(&mut self) -> Self
+    where
+        A: Clone,
+    {
+        Vector {
+            raw: self.raw.take(),
+            allocator: self.allocator.clone(),
+            growth: PhantomData,
+        }
+    }
+
+    /// Converts the vector into `Box<[T]>`.
+    ///
+    /// Since the vector's buffer leaves room for a header before the elements, its allocation
+    /// can't be handed over to a `Box`. The elements are instead moved (not cloned) into a new
+    /// allocation of exactly `len` items from the global allocator, and the vector's buffer is
+    /// deallocated.
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        let len = self.len();
+        let mut boxed = Vec::with_capacity(len);
+        unsafe {
+            ptr::copy_nonoverlapping(self.raw.data_ptr(), boxed.as_mut_ptr(), len);
+            self.raw.header.len = 0;
+            boxed.set_len(len);
+        }
+
+        boxed.into_boxed_slice()
+    }
+
+    /// Consumes and leaks the vector, returning a mutable reference to the contents.
+    ///
+    /// Neither the buffer (including the room left for the header) nor the allocator are
+    /// ever freed. This is mainly useful for data that lives for the remainder of the
+    /// program's life.
+    pub fn leak<'a>(self) -> &'a mut [T]
+    where
+        A: 'a,
+    {
+        let vector = mem::ManuallyDrop::new(self);
+        unsafe { core::slice::from_raw_parts_mut(vector.raw.data_ptr(), vector.len()) }
+    }
+
+    /// Returns an iterator over all contiguous windows of `N` items, as array references.
+    ///
+    /// This is the compile-time sized counterpart of `windows`. The windows overlap, and
+    /// if the vector has fewer than `N` items the iterator yields nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let samples = vector![1.0, 2.0, 6.0, 4.0, 2.0];
+    /// let averages: Vec<f64> = samples
+    ///     .windows_exact::<3>()
+    ///     .map(|[a, b, c]| (a + b + c) / 3.0)
+    ///     .collect();
+    /// assert_eq!(averages, vec![3.0, 4.0, 4.0]);
+    /// ```
+    pub fn windows_exact<const N: usize>(&self) -> WindowsExact<'_, T, N> {
+        WindowsExact::new(self.as_slice())
+    }
+
+    /// Consumes a vector of iterables and returns a vector containing all of their items.
+    ///
+    /// The iterables are converted into iterators first, so that the new vector can be
+    /// allocated using the sum of the lower bounds of their size hints.
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let nested = vector![vec![1, 2], vec![], vec![3, 4, 5]];
+    /// assert_eq!(nested.flatten(), vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn flatten<U>(self) -> Vector<U, A, G>
+    where
+        T: IntoIterator<Item = U>,
+        A: Clone,
+    {
+        let mut iters: Vector<_, A, G> = Vector::with_capacity_in(self.len(), self.allocator.clone());
+        for item in self {
+            iters.push(item.into_iter());
+        }
+
+        let cap = iters.iter().fold(0usize, |cap, iter| cap.saturating_add(iter.size_hint().0));
+        let mut result = Vector::with_capacity_in(cap, iters.allocator.clone());
+        for iter in iters {
+            result.extend(iter);
+        }
+
+        result
+    }
+
+    /// Consumes a vector of vectors, moving all of their items to the back of `dst`.
+    ///
+    /// Unlike [`flatten`](Self::flatten), this does not allocate a temporary vector. The items
+    /// of each inner vector are moved in bulk and the inner buffers are released as they
+    /// are consumed.
+    ///
+    /// ```
+    /// use shared_vector::{vector, Vector};
+    /// let mut dst = vector![0];
+    /// vector![vector![1, 2], Vector::new(), vector![3]].flatten_into(&mut dst);
+    /// assert_eq!(dst, vec![0, 1, 2, 3]);
+    /// ```
+    pub fn flatten_into<U, A2, G2>(self, dst: &mut Vector<U, A2, G2>)
+    where
+        T: Into<Vector<U>>,
+        A2: Allocator,
+        G2: GrowthStrategy,
+    {
+        for item in self {
+            let mut inner: Vector<U> = item.into();
+            inner.drain_all().collect_into_existing(dst);
+        }
+    }
+
+    /// Consumes the vector and splits it into vectors of `chunk_size` items.
+    ///
+    /// The last vector has fewer items if `chunk_size` does not divide the length of the vector.
+    /// This is the inverse of [`flatten`](Self::flatten).
+    ///
+    /// Each vector owns its buffer and a buffer starts with a header, so the allocation of this
+    /// vector can't be partitioned. The items are instead moved (not cloned) into a new
+    /// allocation per chunk, and the buffer of this vector is deallocated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let chunks = vector![1, 2, 3, 4, 5].chunks_as_vecs(2);
+    /// assert_eq!(chunks, vec![vector![1, 2], vector![3, 4], vector![5]]);
+    /// ```
+    pub fn chunks_as_vecs(mut self, chunk_size: usize) -> Vector<Vector<T, A, G>>
+    where
+        A: Clone,
+    {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        let len = self.len();
+        let mut chunks = Vector::with_capacity(len.div_ceil(chunk_size));
+        unsafe {
+            // If an allocation fails the remaining items are leaked rather than dropped twice.
+            self.raw.header.len = 0;
+            let mut start = 0;
+            while start < len {
+                let count = chunk_size.min(len - start);
+                let mut chunk = Vector::with_capacity_in(count, self.allocator.clone());
+                ptr::copy_nonoverlapping(self.raw.data_ptr().add(start), chunk.as_mut_ptr(), count);
+                chunk.set_len(count);
+                chunks.push(chunk);
+                start += count;
+            }
+        }
+
+        chunks
+    }
+
+    /// Consumes the vector, moving the items for which `predicate` returns `true` into
+    /// the first vector and the others into the second one.
+    ///
+    /// The relative order of the items is preserved in both vectors. Both vectors are
+    /// allocated with the length of this vector as capacity so that the items are moved
+    /// in a single pass without reallocating.
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let (even, odd) = vector![1, 2, 3, 4, 5].partition(|x| x % 2 == 0);
+    /// assert_eq!(even, vec![2, 4]);
+    /// assert_eq!(odd, vec![1, 3, 5]);
+    /// ```
+    pub fn partition<F>(self, mut predicate: F) -> (Vector<T, A, G>, Vector<T, A, G>)
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        let len = self.len();
+        let mut left: Vector<T, A, G> = Vector::with_capacity_in(len, self.allocator.clone());
+        let mut right = Vector::with_capacity_in(len, self.allocator.clone());
+
+        for item in self {
+            let dst = if predicate(&item) { &mut left } else { &mut right };
+            unsafe {
+                let idx = dst.len();
+                ptr::write(dst.as_mut_ptr().add(idx), item);
+                dst.set_len(idx + 1);
+            }
+        }
+
+        (left, right)
+    }
+
+    /// Consumes the vector, applying `f` to each item and collecting the results into a
+    /// new vector in the same allocator.
+    ///
+    /// The new vector is allocated with the length of this vector as capacity.
+    ///
+    /// ```
+    /// use shared_vector::vector;
+    /// let words = vector![String::from("a"), String::from("bcd"), String::new()];
+    /// assert_eq!(words.map(|s| s.len()), vec![1, 3, 0]);
+    /// ```
+    pub fn map<U, F>(self, mut f: F) -> Vector<U, A, G>
+    where
+        F: FnMut(T) -> U,
+        A: Clone,
+    {
+        let mut result: Vector<U, A, G> = Vector::with_capacity_in(self.len(), self.allocator.clone());
+        for item in self {
+            let mapped = f(item);
+            unsafe {
+                let idx = result.len();
+                ptr::write(result.as_mut_ptr().add(idx), mapped);
+                result.set_len(idx + 1);
+            }
+        }
+
+        result
+    }
+}
+
+impl<T, A: Allocator, G: GrowthStrategy> Drop for Vector<T, A, G> {
+    fn drop(&mut self) {
+        unsafe {
+            self.raw.deallocate(&self.allocator)
+        }
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone, G: GrowthStrategy> Clone for Vector<T, A, G> {
+    fn clone(&self) -> Self {
+        self.clone_buffer()
+    }
+
+    /// Overwrites the contents of `self` with a clone of the contents of `source`.
+    ///
+    /// The existing items are cloned into in place and the buffer is only reallocated
+    /// if `source` has more items than `self` can hold.
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len());
+        let (init, tail) = source.split_at(self.len());
+        self.as_mut_slice().clone_from_slice(init);
+        self.extend_from_slice(tail);
+    }
+}
+
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<Vector<T, A, G>> for Vector<T, A, G> {
+    fn eq(&self, other: &Self) -> bool {
         self.as_slice() == other.as_slice()
     }
 }
 
-impl<T: PartialEq<T>, A: Allocator> PartialEq<&[T]> for Vector<T, A> {
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<&[T]> for Vector<T, A, G> {
     fn eq(&self, other: &&[T]) -> bool {
         self.as_slice() == *other
     }
 }
 
-impl<T, A: Allocator> AsRef<[T]> for Vector<T, A> {
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<[T]> for Vector<T, A, G> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+/// Compares the items of a `Vector` and a `Vec`.
+///
+/// ```
+/// use shared_vector::vector;
+/// let v = vector![1, 2, 3];
+/// assert_eq!(v, vec![1, 2, 3]);
+/// assert_eq!(vec![1, 2, 3], v);
+/// ```
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<Vec<T>> for Vector<T, A, G> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<Vector<T, A, G>> for Vec<T> {
+    fn eq(&self, other: &Vector<T, A, G>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<Vector<T, A, G>> for [T] {
+    fn eq(&self, other: &Vector<T, A, G>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<T>, A: Allocator, G: GrowthStrategy> PartialEq<Vector<T, A, G>> for &[T] {
+    fn eq(&self, other: &Vector<T, A, G>) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl<T: Eq, A: Allocator, G: GrowthStrategy> Eq for Vector<T, A, G> {}
+
+impl<T, A: Allocator, G: GrowthStrategy> AsRef<[T]> for Vector<T, A, G> {
     fn as_ref(&self) -> &[T] {
         self.as_slice()
     }
 }
 
-impl<T, A: Allocator> AsMut<[T]> for Vector<T, A> {
+impl<T, A: Allocator, G: GrowthStrategy> AsMut<[T]> for Vector<T, A, G> {
     fn as_mut(&mut self) -> &mut [T] {
         self.as_mut_slice()
     }
 }
 
-impl<T, A: Allocator> Into<(A, RawVector<T>)> for Vector<T, A> {
-    fn into(mut self) -> (A, RawVector<T>) {
-        let allocator = mem::replace(&mut self.allocator, unsafe { mem::uninitialized() });
-        let raw = self.raw.take();
-        
-        mem::forget(self);
+impl<T, A: Allocator, G: GrowthStrategy> Borrow<[T]> for Vector<T, A, G> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: Allocator, G: GrowthStrategy> BorrowMut<[T]> for Vector<T, A, G> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+/// Borrows the items of the vector without copying them.
+impl<'a, T: Clone, A: Allocator, G: GrowthStrategy> From<&'a Vector<T, A, G>> for Cow<'a, [T]> {
+    fn from(vector: &'a Vector<T, A, G>) -> Self {
+        Cow::Borrowed(vector.as_slice())
+    }
+}
+
+/// Clones the items if the `Cow` is borrowed, and moves them if it is owned.
+///
+/// `[T]` is already `ToOwned` with `Vec<T>` as the owned type, so a `Cow<[T]>` holds a `Vec`
+/// when it is owned. This conversion is the way back to a `Vector` at the end of a
+/// copy-on-write pipeline, and it copies the items at most once.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use shared_vector::{vector, Vector};
+///
+/// fn normalize(items: &[i32]) -> Cow<[i32]> {
+///     if items.iter().all(|x| *x >= 0) {
+///         return Cow::Borrowed(items);
+///     }
+///     Cow::Owned(items.iter().map(|x| x.abs()).collect())
+/// }
+///
+/// let input = vector![1, -2, 3];
+/// let output: Vector<i32> = normalize(&input).into();
+/// assert_eq!(output, vec![1, 2, 3]);
+/// ```
+impl<T: Clone> From<Cow<'_, [T]>> for Vector<T, Global> {
+    fn from(cow: Cow<'_, [T]>) -> Self {
+        match cow {
+            Cow::Borrowed(slice) => Vector::from_slice(slice),
+            Cow::Owned(vec) => Vector::from(vec),
+        }
+    }
+}
+
+impl<T, A: Allocator, G: GrowthStrategy> From<Vector<T, A, G>> for (A, RawVector<T>) {
+    fn from(mut vector: Vector<T, A, G>) -> (A, RawVector<T>) {
+        let allocator = unsafe { ptr::read(&vector.allocator) };
+        let raw = vector.raw.take();
+
+        mem::forget(vector);
 
         (allocator, raw)
     }
 }
 
-impl<T> Default for Vector<T, Global> {
+impl<T, A: Allocator + Default, G: GrowthStrategy> Default for Vector<T, A, G> {
     fn default() -> Self {
-        Self::new()
+        Self::new_in(A::default())
     }
 }
 
-impl<'a, T, A: Allocator> IntoIterator for &'a Vector<T, A> {
+impl<T, A: Allocator, G: GrowthStrategy> IntoIterator for Vector<T, A, G> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+    fn into_iter(self) -> IntoIter<T, A> {
+        let (allocator, mut raw): (A, RawVector<T>) = self.into();
+        let end = raw.len();
+        raw.header.len = 0;
+
+        IntoIter { raw, allocator, start: 0, end }
+    }
+}
+
+impl<'a, T, A: Allocator, G: GrowthStrategy> IntoIterator for &'a Vector<T, A, G> {
     type Item = &'a T;
     type IntoIter = core::slice::Iter<'a, T>;
     fn into_iter(self) -> core::slice::Iter<'a, T> {
@@ -1432,7 +2861,7 @@ impl<'a, T, A: Allocator> IntoIterator for &'a Vector<T, A> {
     }
 }
 
-impl<'a, T, A: Allocator> IntoIterator for &'a mut Vector<T, A> {
+impl<'a, T, A: Allocator, G: GrowthStrategy> IntoIterator for &'a mut Vector<T, A, G> {
     type Item = &'a mut T;
     type IntoIter = core::slice::IterMut<'a, T>;
     fn into_iter(self) -> core::slice::IterMut<'a, T> {
@@ -1440,7 +2869,7 @@ impl<'a, T, A: Allocator> IntoIterator for &'a mut Vector<T, A> {
     }
 }
 
-impl<T, A: Allocator, I> Index<I> for Vector<T, A>
+impl<T, A: Allocator, G: GrowthStrategy, I> Index<I> for Vector<T, A, G>
 where
     I: core::slice::SliceIndex<[T]>,
 {
@@ -1450,7 +2879,7 @@ where
     }
 }
 
-impl<T, A: Allocator, I> IndexMut<I> for Vector<T, A>
+impl<T, A: Allocator, G: GrowthStrategy, I> IndexMut<I> for Vector<T, A, G>
 where
     I: core::slice::SliceIndex<[T]>,
 {
@@ -1459,25 +2888,197 @@ where
     }
 }
 
-impl<T, A: Allocator> Deref for Vector<T, A> {
+impl<T, A: Allocator, G: GrowthStrategy> Deref for Vector<T, A, G> {
     type Target = [T];
     fn deref(&self) -> &[T] {
         self.as_slice()
     }
 }
 
-impl<T, A: Allocator> DerefMut for Vector<T, A> {
+impl<T, A: Allocator, G: GrowthStrategy> DerefMut for Vector<T, A, G> {
     fn deref_mut(&mut self) -> &mut [T] {
         self.as_mut_slice()
     }
 }
 
-impl<T: Debug, A: Allocator> Debug for Vector<T, A> {
+impl<T: Debug, A: Allocator, G: GrowthStrategy> Debug for Vector<T, A, G> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         self.as_slice().fmt(f)
     }
 }
 
+/// Moves the items of a `Vec` into a new `Vector`.
+///
+/// The allocation of the `Vec` cannot be reused because a `Vector`'s buffer starts
+/// with a header that a `Vec`'s buffer does not have room for. The items are moved
+/// into a newly allocated buffer with a single copy and are not cloned.
+impl<T> From<Vec<T>> for Vector<T, Global> {
+    fn from(mut vec: Vec<T>) -> Self {
+        let len = vec.len();
+        let mut vector = Vector::with_capacity(len);
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr(), vector.as_mut_ptr(), len);
+            vec.set_len(0);
+            vector.set_len(len);
+        }
+
+        vector
+    }
+}
+
+/// Moves the items of a boxed slice into a new `Vector`.
+///
+/// See the conversion from `Vec<T>` for details about the allocation.
+impl<T> From<Box<[T]>> for Vector<T, Global> {
+    fn from(boxed: Box<[T]>) -> Self {
+        Vector::from(boxed.into_vec())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, Global> {
+    fn from(array: [T; N]) -> Self {
+        let array = mem::ManuallyDrop::new(array);
+        let mut vector = Vector::with_capacity(N);
+        unsafe {
+            ptr::copy_nonoverlapping(array.as_ptr(), vector.as_mut_ptr(), N);
+            vector.set_len(N);
+        }
+
+        vector
+    }
+}
+
+impl<T: Clone> From<&[T]> for Vector<T, Global> {
+    fn from(slice: &[T]) -> Self {
+        Vector::from_slice(slice)
+    }
+}
+
+/// Moves the items of a `Vector` into a new `Vec`.
+///
+/// See [`Vector::into_boxed_slice`].
+impl<T, A: Allocator, G: GrowthStrategy> From<Vector<T, A, G>> for Vec<T> {
+    fn from(vector: Vector<T, A, G>) -> Self {
+        vector.into_boxed_slice().into_vec()
+    }
+}
+
+/// Copies the bytes of the string into a new vector.
+impl From<String> for Vector<u8, Global> {
+    fn from(s: String) -> Self {
+        Vector::from(s.into_bytes())
+    }
+}
+
+/// Copies the bytes into a new string, or returns the vector if they are not valid UTF-8.
+impl<A: Allocator, G: GrowthStrategy> TryFrom<Vector<u8, A, G>> for String {
+    type Error = Vector<u8, A, G>;
+
+    fn try_from(bytes: Vector<u8, A, G>) -> Result<Self, Self::Error> {
+        bytes.into_string()
+    }
+}
+
+impl<T, A: Allocator, G: GrowthStrategy> Extend<T> for Vector<T, A, G> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        Vector::extend(self, iter)
+    }
+}
+
+impl<'a, T: Copy + 'a, A: Allocator, G: GrowthStrategy> Extend<&'a T> for Vector<T, A, G> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        Vector::extend(self, iter.into_iter().copied())
+    }
+}
+
+impl<T> FromIterator<T> for Vector<T, Global> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Vector::new();
+        Vector::extend(&mut vector, iter);
+        vector
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: Allocator, G: GrowthStrategy> std::io::Write for Vector<u8, A, G> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<A: Allocator, G: GrowthStrategy> Vector<u8, A, G> {
+    /// Converts a vector of bytes into a `String`, or returns the bytes along with the
+    /// error if they are not valid UTF-8.
+    ///
+    /// The bytes are copied into the string's own allocation.
+    ///
+    /// ```
+    /// use shared_vector::{vector, Vector};
+    /// assert_eq!(Vector::from_utf8(vector![b'h', b'i']).unwrap(), "hi");
+    /// let (bytes, _err) = Vector::from_utf8(vector![0xffu8]).unwrap_err();
+    /// assert_eq!(bytes, vec![0xff]);
+    /// ```
+    pub fn from_utf8(bytes: Self) -> Result<String, (Self, core::str::Utf8Error)> {
+        match core::str::from_utf8(bytes.as_slice()) {
+            Ok(s) => Ok(String::from(s)),
+            Err(e) => Err((bytes, e)),
+        }
+    }
+
+    /// Converts the vector into a `String`, or returns it unchanged if its contents are
+    /// not valid UTF-8.
+    ///
+    /// Like [`from_utf8`](Self::from_utf8), this copies the bytes into the string's own
+    /// allocation.
+    pub fn into_string(self) -> Result<String, Self> {
+        Self::from_utf8(self).map_err(|(bytes, _)| bytes)
+    }
+
+    /// Returns the contents of the vector as a string slice if they are valid UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(self.as_slice())
+    }
+
+    /// Returns the contents of the vector as a string slice without checking that they
+    /// are valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The contents of the vector must be valid UTF-8.
+    #[inline]
+    pub unsafe fn as_str_unchecked(&self) -> &str {
+        core::str::from_utf8_unchecked(self.as_slice())
+    }
+
+    /// Appends the bytes of a string slice to the back of the vector.
+    ///
+    /// ```
+    /// use shared_vector::Vector;
+    /// let mut buffer = Vector::new();
+    /// buffer.push_str("shared");
+    /// buffer.push_str(" vector");
+    /// assert_eq!(buffer.as_str(), Ok("shared vector"));
+    /// ```
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+}
+
 impl<T: Clone, A: Allocator + Clone> From<SharedVector<T, A>> for Vector<T, A> {
     fn from(shared: SharedVector<T, A>) -> Self {
         shared.into_unique()
@@ -1490,12 +3091,24 @@ impl<T: Clone, A: Allocator + Clone> From<AtomicSharedVector<T, A>> for Vector<T
     }
 }
 
-impl<T: core::hash::Hash, A: Allocator> core::hash::Hash for Vector<T, A> {
+impl<T: core::hash::Hash, A: Allocator, G: GrowthStrategy> core::hash::Hash for Vector<T, A, G> {
     fn hash<H>(&self, state: &mut H) where H: core::hash::Hasher {
         self.as_slice().hash(state)
     }
 }
 
+impl<T: PartialOrd<T>, A: Allocator, G: GrowthStrategy> PartialOrd<Vector<T, A, G>> for Vector<T, A, G> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, A: Allocator, G: GrowthStrategy> Ord for Vector<T, A, G> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
 #[test]
 fn bump_alloc() {
     use blink_alloc::BlinkAlloc;
@@ -1585,6 +3198,18 @@ fn basic_unique() {
 fn shrink() {
     let mut v: Vector<u32> = Vector::with_capacity(32);
     v.shrink_to(8);
+    assert_eq!(v.capacity(), 8);
+
+    v.extend(0..4);
+    v.shrink_to(2);
+    assert_eq!(v.capacity(), 4);
+    assert_eq!(v.as_slice(), &[0, 1, 2, 3]);
+
+    v.clear();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 0);
+    v.push(1);
+    assert_eq!(v.as_slice(), &[1]);
 }
 
 #[test]
@@ -1644,8 +3269,8 @@ fn borrowd_dyn_alloc() {
 #[test]
 fn splice1() {
     let mut vec = Vector::new();
-    vec.splice(0..0, vec![Box::new(1); 5].into_iter());
-    vec.splice(0..0, vec![Box::new(2); 5].into_iter());
+    vec.splice(0..0, vec![Box::new(1); 5]);
+    vec.splice(0..0, vec![Box::new(2); 5]);
 }
 
 #[test]
@@ -1664,3 +3289,975 @@ fn drain1() {
     let end = 16059518370053021185.min(len);
     vectors[2].drain(start..end);
 }
+
+#[test]
+fn retain() {
+    let mut v = crate::vector![Box::new(1u32), Box::new(2), Box::new(3), Box::new(4), Box::new(5)];
+    v.retain(|val| **val % 2 == 1);
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(3), Box::new(5)]);
+
+    v.retain_mut(|val| {
+        **val *= 10;
+        **val != 30
+    });
+    assert_eq!(v.as_slice(), &[Box::new(10), Box::new(50)]);
+
+    // A panicking predicate must leave the vector in a consistent state.
+    let mut v = crate::vector![Box::new(1u32), Box::new(2), Box::new(3), Box::new(4)];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.retain(|val| {
+            if **val == 3 {
+                panic!();
+            }
+            **val != 2
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(3), Box::new(4)]);
+}
+
+#[test]
+fn dedup() {
+    let mut v = crate::vector![Box::new(1u32), Box::new(2), Box::new(3)];
+    v.dedup();
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+
+    let mut v = crate::vector![Box::new(7u32), Box::new(7), Box::new(7), Box::new(7)];
+    v.dedup();
+    assert_eq!(v.as_slice(), &[Box::new(7)]);
+
+    let mut v = crate::vector![Box::new(1u32), Box::new(1), Box::new(2), Box::new(3), Box::new(3), Box::new(3)];
+    v.dedup();
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+
+    let mut v = crate::vector![10u32, 11, 20, 21, 22, 30];
+    v.dedup_by_key(|val| *val / 10);
+    assert_eq!(v.as_slice(), &[10, 20, 30]);
+
+    let mut v = crate::vector!["foo", "FOO", "bar", "Bar", "baz"];
+    v.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    assert_eq!(v.as_slice(), &["foo", "bar", "baz"]);
+}
+
+#[test]
+fn sort() {
+    // Sorting is provided by the slice methods through `DerefMut`.
+    let mut v = crate::vector![5u32, 1, 4, 2, 3];
+    v.sort();
+    assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5]);
+    v.sort_by(|a, b| b.cmp(a));
+    assert_eq!(v.as_slice(), &[5, 4, 3, 2, 1]);
+    v.sort_by_key(|val| *val % 3);
+    assert_eq!(v.as_slice(), &[3, 4, 1, 5, 2]);
+    v.sort_by_cached_key(|val| val.to_string());
+    assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5]);
+    v.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(v.as_slice(), &[5, 4, 3, 2, 1]);
+    v.sort_unstable();
+    assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5]);
+    v.sort_unstable_by_key(|val| core::cmp::Reverse(*val));
+    assert_eq!(v.as_slice(), &[5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn binary_search_insert() {
+    // Binary search is provided by the slice methods through `Deref`.
+    // Inserting at the returned position must keep the vector sorted.
+    let mut v: Vector<u32> = Vector::new();
+    let mut rng = 12345u32;
+    for _ in 0..500 {
+        rng = rng.wrapping_mul(1103515245).wrapping_add(12345);
+        let val = (rng >> 16) % 200;
+        match v.binary_search(&val) {
+            Ok(idx) => assert_eq!(v[idx], val),
+            Err(idx) => v.insert(idx, val),
+        }
+        assert!(v.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    assert_eq!(v.binary_search_by(|probe| probe.cmp(&v[3])), Ok(3));
+    assert_eq!(v.binary_search_by_key(&(v[5] * 2), |val| val * 2), Ok(5));
+}
+
+#[test]
+fn split_off() {
+    fn num(val: u32) -> Box<u32> {
+        Box::new(val)
+    }
+
+    let mut v = crate::vector![num(0), num(1), num(2), num(3)];
+    let cap = v.capacity();
+
+    let tail = v.split_off(4);
+    assert!(tail.is_empty());
+    assert_eq!(v.len(), 4);
+
+    let tail = v.split_off(2);
+    assert_eq!(v.as_slice(), &[num(0), num(1)]);
+    assert_eq!(tail.as_slice(), &[num(2), num(3)]);
+    assert_eq!(v.capacity(), cap);
+
+    let tail = v.split_off(0);
+    assert!(v.is_empty());
+    assert_eq!(tail.as_slice(), &[num(0), num(1)]);
+}
+
+#[test]
+fn append() {
+    let mut a = crate::vector![Box::new(1u32), Box::new(2)];
+    let mut b = crate::vector![Box::new(3u32), Box::new(4), Box::new(5)];
+    let b_cap = b.capacity();
+    a.append(&mut b);
+    assert_eq!(a.as_slice(), &[Box::new(1), Box::new(2), Box::new(3), Box::new(4), Box::new(5)]);
+    assert!(b.is_empty());
+    assert_eq!(b.capacity(), b_cap);
+
+    a.append(&mut b);
+    assert_eq!(a.len(), 5);
+}
+
+#[test]
+fn extend_from_within() {
+    fn num(val: u32) -> Box<u32> {
+        Box::new(val)
+    }
+
+    let mut v = Vector::with_capacity(3);
+    v.extend_from_slice(&[num(0), num(1), num(2)]);
+    assert_eq!(v.capacity(), 3);
+
+    // Doubling, triggers a reallocation.
+    v.extend_from_within(..);
+    assert_eq!(v.as_slice(), &[num(0), num(1), num(2), num(0), num(1), num(2)]);
+    assert!(v.capacity() >= 6);
+
+    // Prefix.
+    v.extend_from_within(..2);
+    assert_eq!(&v[6..], &[num(0), num(1)]);
+
+    // Suffix.
+    v.extend_from_within(6..);
+    assert_eq!(&v[8..], &[num(0), num(1)]);
+
+    v.extend_from_within(3..3);
+    assert_eq!(v.len(), 10);
+}
+
+#[test]
+fn resize() {
+    let mut v: Vector<Box<u32>> = Vector::new();
+    v.resize(3, Box::new(1));
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(1), Box::new(1)]);
+
+    v.resize(1, Box::new(2));
+    assert_eq!(v.as_slice(), &[Box::new(1)]);
+
+    let mut counter = 1;
+    v.resize_with(4, || {
+        counter += 1;
+        Box::new(counter)
+    });
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3), Box::new(4)]);
+
+    v.resize_with(0, || unreachable!());
+    assert!(v.is_empty());
+}
+
+#[test]
+fn truncate() {
+    use std::cell::RefCell;
+
+    struct Recorder<'a>(u32, &'a RefCell<Vec<u32>>);
+    impl Drop for Recorder<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    let dropped = RefCell::new(Vec::new());
+    let mut v = Vector::new();
+    for i in 0..5 {
+        v.push(Recorder(i, &dropped));
+    }
+
+    v.truncate(5);
+    assert!(dropped.borrow().is_empty());
+
+    // Like `Vec`, the truncated elements are dropped in order.
+    v.truncate(2);
+    assert_eq!(v.len(), 2);
+    assert_eq!(dropped.borrow().as_slice(), &[2, 3, 4]);
+
+    v.truncate(0);
+    assert!(v.is_empty());
+    assert_eq!(dropped.borrow().as_slice(), &[2, 3, 4, 0, 1]);
+}
+
+#[test]
+fn try_push() {
+    use core::alloc::Layout;
+
+    struct FailingAllocator;
+    unsafe impl Allocator for FailingAllocator {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+            unreachable!()
+        }
+    }
+
+    let mut v: Vector<Box<u32>, FailingAllocator> = Vector::new_in(FailingAllocator);
+    assert_eq!(v.try_push(Box::new(1)), Err(Box::new(1)));
+    assert_eq!(v.try_insert(0, Box::new(2)), Err(Box::new(2)));
+    assert!(v.is_empty());
+    assert_eq!(v.capacity(), 0);
+
+    let mut v = Vector::new();
+    assert_eq!(v.try_push(Box::new(1)), Ok(()));
+    assert_eq!(v.try_insert(0, Box::new(0)), Ok(()));
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(1)]);
+}
+
+#[test]
+fn spare_capacity() {
+    // Simulates a C API that fills a buffer and returns the amount of bytes written.
+    fn read_into(dst: &mut [MaybeUninit<u8>], src: &[u8]) -> usize {
+        let n = dst.len().min(src.len());
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr() as *mut u8, n);
+        }
+        n
+    }
+
+    let mut v: Vector<u8> = Vector::with_capacity(16);
+    v.push(0);
+    let spare = v.spare_capacity_mut();
+    assert_eq!(spare.len(), 15);
+    let n = read_into(spare, b"hello");
+    unsafe {
+        v.set_len(v.len() + n);
+    }
+    assert_eq!(v.as_slice(), b"\0hello");
+    assert_eq!(v.spare_capacity_mut().len(), 10);
+}
+
+#[test]
+fn into_boxed_slice() {
+    let v = crate::vector![Box::new(1u32), Box::new(2), Box::new(3)];
+    let boxed = v.into_boxed_slice();
+    assert_eq!(&boxed[..], &[Box::new(1), Box::new(2), Box::new(3)]);
+
+    let mut v = Vector::new();
+    v.extend(boxed.into_vec());
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+
+    let empty: Vector<u32> = Vector::new();
+    assert!(empty.into_boxed_slice().is_empty());
+}
+
+#[test]
+fn leak() {
+    let v = crate::vector![1u32, 2, 3];
+    let cap = v.capacity();
+    let table: &'static mut [u32] = v.leak();
+    assert_eq!(table, &[1, 2, 3]);
+    table[1] = 20;
+    assert_eq!(table, &[1, 20, 3]);
+
+    let empty: &'static mut [u32] = Vector::new().leak();
+    assert!(empty.is_empty());
+
+    // Reconstruct the vector to avoid reporting the leak when running under miri.
+    let v: Vector<u32> = Vector {
+        raw: RawVector {
+            data: NonNull::new(table.as_mut_ptr()).unwrap(),
+            header: VecHeader { len: 3, cap: cap as BufferSize },
+        },
+        allocator: Global,
+        growth: PhantomData,
+    };
+    assert_eq!(v.as_slice(), &[1, 20, 3]);
+}
+
+#[test]
+fn partial_eq() {
+    let a = crate::vector![1u32, 2, 3];
+    let b = crate::vector![1u32, 2, 3];
+    let c = crate::vector![1u32, 2];
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    assert_eq!(a, vec![1, 2, 3]);
+    assert_eq!(vec![1, 2, 3], a);
+    assert_ne!(c, vec![1, 2, 3]);
+
+    let s: &[u32] = &[1, 2, 3];
+    assert_eq!(a, s);
+    assert_eq!(s, a);
+    assert!(a == *s);
+    assert!(*s == a);
+    assert!(*s != c);
+}
+
+#[test]
+fn hash_and_ord_match_vec() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeSet, HashMap};
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of<H: Hash>(val: &H) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        val.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // A small deterministic set of inputs including empty vectors, common prefixes
+    // and differing lengths.
+    let mut inputs: Vec<Vec<u32>> = vec![vec![]];
+    let mut seed = 7u32;
+    for len in 0..6 {
+        for _ in 0..4 {
+            let mut v = Vec::new();
+            for _ in 0..len {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                v.push((seed >> 16) % 4);
+            }
+            inputs.push(v);
+        }
+    }
+
+    for a in &inputs {
+        let va = Vector::from_slice(a);
+        assert_eq!(hash_of(&va), hash_of(a));
+        for b in &inputs {
+            let vb = Vector::from_slice(b);
+            assert_eq!(va.cmp(&vb), a.cmp(b));
+            assert_eq!(va.partial_cmp(&vb), a.partial_cmp(b));
+        }
+    }
+
+    let set: BTreeSet<Vector<u32>> = inputs.iter().map(|v| Vector::from_slice(v)).collect();
+    let expected: BTreeSet<Vec<u32>> = inputs.iter().cloned().collect();
+    assert!(set.iter().map(|v| v.as_slice()).eq(expected.iter().map(|v| v.as_slice())));
+
+    let mut map = HashMap::new();
+    map.insert(crate::vector![1u32, 2], "a");
+    assert_eq!(map.get(&crate::vector![1u32, 2]), Some(&"a"));
+    assert_eq!(map.get(&crate::vector![2u32, 1]), None);
+}
+
+#[test]
+fn index_ranges() {
+    let mut v = crate::vector![0u32, 1, 2, 3, 4];
+
+    assert_eq!(v[2], 2);
+    assert_eq!(&v[1..3], &[1, 2]);
+    assert_eq!(&v[..2], &[0, 1]);
+    assert_eq!(&v[3..], &[3, 4]);
+    assert_eq!(&v[..], &[0, 1, 2, 3, 4]);
+    assert_eq!(&v[1..=3], &[1, 2, 3]);
+    assert_eq!(&v[..=1], &[0, 1]);
+    assert_eq!(&v[5..], &[]);
+
+    v[0] = 10;
+    v[1..3].copy_from_slice(&[11, 12]);
+    v[..=0][0] += 1;
+    v[3..].fill(0);
+    assert_eq!(v.as_slice(), &[11, 11, 12, 0, 0]);
+
+    let result = std::panic::catch_unwind(|| {
+        let v = crate::vector![0u32, 1, 2];
+        v[3]
+    });
+    let msg = result.unwrap_err();
+    let msg = msg.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("the len is 3"), "{msg}");
+}
+
+#[test]
+fn from_conversions() {
+    let v: Vector<Box<u32>> = vec![Box::new(1), Box::new(2), Box::new(3)].into();
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+
+    let back: Vec<Box<u32>> = v.into();
+    assert_eq!(back, vec![Box::new(1), Box::new(2), Box::new(3)]);
+
+    let boxed: Box<[String]> = vec![String::from("a"), String::from("b")].into_boxed_slice();
+    let v = Vector::from(boxed);
+    assert_eq!(v.as_slice(), &[String::from("a"), String::from("b")]);
+
+    let v = Vector::from([Box::new(1u32), Box::new(2)]);
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2)]);
+
+    let v = Vector::from(&[1u32, 2, 3][..]);
+    assert_eq!(v.as_slice(), &[1, 2, 3]);
+
+    let v = Vector::from(Vec::<u32>::new());
+    assert!(v.is_empty());
+    let v: Vector<()> = Vector::from([(), ()]);
+    assert_eq!(Vec::from(v).len(), 2);
+}
+
+#[test]
+fn extend_and_collect() {
+    let v: Vector<u32> = (0..5).collect();
+    assert_eq!(v.as_slice(), &[0, 1, 2, 3, 4]);
+
+    let mut v: Vector<Box<u32>> = (0..3).map(Box::new).collect();
+    Extend::extend(&mut v, vec![Box::new(3), Box::new(4)]);
+    assert_eq!(v.len(), 5);
+    assert_eq!(*v[4], 4);
+
+    let mut v: Vector<u32> = Vector::new();
+    Extend::extend(&mut v, &[1, 2, 3]);
+    Extend::extend(&mut v, [4, 5].iter());
+    assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5]);
+
+    // An iterator that does not report an upper bound in its size hint
+    // must not lose items when the capacity runs out.
+    let mut n = 0;
+    let v: Vector<u32> = std::iter::from_fn(|| { n += 1; if n <= 100 { Some(n) } else { None } }).collect();
+    assert_eq!(v.len(), 100);
+    assert!(v.iter().copied().eq(1..=100));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn io_write() {
+    use std::io::Write;
+
+    let mut v: Vector<u8> = Vector::new();
+    assert_eq!(v.write(b"abc").unwrap(), 3);
+    v.write_all(b"def").unwrap();
+    write!(v, "{}-{}", 1, 2).unwrap();
+    v.flush().unwrap();
+    assert_eq!(v.as_slice(), b"abcdef1-2");
+}
+
+#[test]
+fn flatten() {
+    let nested: Vector<Vec<i32>> = crate::vector![vec![1, 2, 3], vec![], vec![4], vec![5, 6]];
+    let flat = nested.flatten();
+    assert_eq!(flat, [1, 2, 3, 4, 5, 6].to_vec());
+    assert_eq!(flat.capacity(), 6);
+
+    let nested = crate::vector![crate::vector![String::from("a")], crate::vector![String::from("b")]];
+    assert_eq!(nested.flatten(), vec![String::from("a"), String::from("b")]);
+
+    let empty: Vector<[u32; 2]> = Vector::new();
+    assert!(empty.flatten().is_empty());
+}
+
+#[test]
+fn partition() {
+    let v: Vector<Box<u32>> = (0..10).map(Box::new).collect();
+    let (even, odd) = v.partition(|x| **x % 2 == 0);
+    assert_eq!(even, (0..10).step_by(2).map(Box::new).collect::<Vec<_>>());
+    assert_eq!(odd, (1..10).step_by(2).map(Box::new).collect::<Vec<_>>());
+
+    let (all, none) = crate::vector![1u32, 2, 3].partition(|_| true);
+    assert_eq!(all, vec![1, 2, 3]);
+    assert!(none.is_empty());
+
+    let (a, b) = Vector::<u32>::new().partition(|_| true);
+    assert!(a.is_empty() && b.is_empty());
+}
+
+#[test]
+fn swap_remove() {
+    let mut v = crate::vector![1u32, 2, 3, 4];
+    assert_eq!(v.swap_remove(0), 1);
+    assert_eq!(v.as_slice(), &[4, 2, 3]);
+    assert_eq!(v.swap_remove(2), 3);
+    assert_eq!(v.as_slice(), &[4, 2]);
+    assert_eq!(v.remove(0), 4);
+    assert_eq!(v.as_slice(), &[2]);
+
+    let result = std::panic::catch_unwind(|| {
+        let mut v = crate::vector![1u32, 2];
+        v.swap_remove(2)
+    });
+    let msg = result.unwrap_err();
+    let msg = msg.downcast_ref::<String>().unwrap();
+    assert_eq!(msg, "swap_remove index (is 2) should be < len (is 2)");
+}
+
+#[test]
+fn default() {
+    #[derive(Default)]
+    struct Foo {
+        a: Vector<u32>,
+        b: crate::SharedVector<String>,
+        c: crate::AtomicSharedVector<u8>,
+    }
+
+    let foo = Foo::default();
+    assert!(foo.a.is_empty());
+    assert_eq!(foo.a.capacity(), 0);
+    assert!(foo.b.is_empty());
+    assert!(foo.c.is_empty());
+}
+
+#[test]
+fn clone_from() {
+    let src = crate::vector![String::from("a"), String::from("b"), String::from("c")];
+
+    let mut dst = Vector::with_capacity(10);
+    dst.push(String::from("x"));
+    let ptr = dst.as_ptr();
+    dst.clone_from(&src);
+    assert_eq!(dst, src);
+    assert_eq!(dst.as_ptr(), ptr);
+
+    let mut dst = crate::vector![String::from("x"); 5];
+    dst.clone_from(&src);
+    assert_eq!(dst, src);
+    assert_eq!(dst.capacity(), 5);
+
+    let mut dst = Vector::new();
+    dst.clone_from(&src);
+    assert_eq!(dst, src);
+}
+
+#[test]
+fn len_le_capacity() {
+    fn check<T>(v: &Vector<T>) {
+        assert!(v.len() <= v.capacity(), "len {} > capacity {}", v.len(), v.capacity());
+    }
+
+    let mut v: Vector<u32> = Vector::new();
+    check(&v);
+    for i in 0..100 {
+        v.push(i);
+        check(&v);
+    }
+    v.reserve(1000);
+    check(&v);
+    v.truncate(10);
+    v.shrink_to_fit();
+    check(&v);
+    assert_eq!(v.capacity(), 10);
+    v.extend_from_slice(&[1, 2, 3]);
+    check(&v);
+    v.reserve_exact(5);
+    check(&v);
+    v.insert(0, 42);
+    check(&v);
+    v.drain(2..5);
+    check(&v);
+    v.shrink_to(0);
+    check(&v);
+    v.clear();
+    v.shrink_to_fit();
+    check(&v);
+    assert_eq!(v.capacity(), 0);
+}
+
+#[test]
+fn zst_capacity() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    struct Zst;
+    impl Drop for Zst {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut v = Vector::new();
+    assert_eq!(v.capacity(), BufferSize::MAX as usize);
+    v.push(Zst);
+    assert_eq!(v.capacity(), BufferSize::MAX as usize);
+    let ptr = v.as_ptr();
+    for _ in 0..99 {
+        v.push(Zst);
+    }
+    // Pushing never needs to reallocate.
+    assert_eq!(v.as_ptr(), ptr);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), BufferSize::MAX as usize);
+    assert_eq!(v.len(), 100);
+
+    v.drain(10..20);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 10);
+    assert_eq!(v.len(), 90);
+
+    let mut drain = v.drain(..50);
+    drain.next();
+    drop(drain);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 60);
+    assert_eq!(v.len(), 40);
+
+    v.truncate(30);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 70);
+    let shared = v.into_shared();
+    assert_eq!(shared.capacity(), BufferSize::MAX as usize);
+    let mut v = shared.try_unique().unwrap();
+    assert_eq!(v.len(), 30);
+    v.pop();
+    drop(v);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 100);
+
+    // The length can reach u32::MAX, but not beyond.
+    let mut v: Vector<()> = Vector::with_capacity(1);
+    unsafe { v.set_len(BufferSize::MAX as usize - 1) };
+    v.push(());
+    assert_eq!(v.len(), BufferSize::MAX as usize);
+    assert_eq!(v.push_within_capacity(()), Err(()));
+    unsafe { v.set_len(0) };
+}
+
+#[test]
+fn zst_never_allocates() {
+    use core::alloc::Layout;
+    use core::cell::Cell;
+
+    #[derive(Default)]
+    struct CountingAllocator {
+        allocations: Cell<usize>,
+        deallocations: Cell<usize>,
+    }
+    unsafe impl Allocator for CountingAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocations.set(self.allocations.get() + 1);
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.deallocations.set(self.deallocations.get() + 1);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    let allocator = CountingAllocator::default();
+    let mut v: Vector<(), &CountingAllocator> = Vector::with_capacity_in(10, &allocator);
+    for _ in 0..1000 {
+        v.push(());
+    }
+    v.reserve(100);
+    v.shrink_to_fit();
+    v.extend_from_slice(&[(), ()]);
+    let w = v.clone();
+    v.drain(..500);
+    assert_eq!((v.len(), w.len()), (502, 1002));
+    assert_eq!(v.capacity(), BufferSize::MAX as usize);
+    drop(w);
+    assert_eq!(allocator.allocations.get(), 0);
+
+    // The header is only allocated once the vector is shared, and released when it is unique again.
+    let shared = v.into_shared();
+    assert_eq!(allocator.allocations.get(), 1);
+    let v = shared.try_unique().unwrap();
+    assert_eq!(allocator.deallocations.get(), 1);
+    assert_eq!(v.len(), 502);
+    drop(v);
+    assert_eq!((allocator.allocations.get(), allocator.deallocations.get()), (1, 1));
+}
+
+#[test]
+fn clear_keeps_capacity() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    struct Foo;
+    impl Drop for Foo {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut v = Vector::with_capacity(16);
+    for _ in 0..10 {
+        v.push(Foo);
+    }
+    let cap = v.capacity();
+
+    v.clear();
+    assert_eq!(v.len(), 0);
+    assert_eq!(v.capacity(), cap);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 10);
+
+    v.clear();
+    assert_eq!(DROPS.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn insert_remove() {
+    let mut v = Vector::new();
+    for i in 0..100u32 {
+        v.insert(0, i);
+    }
+    let expected: Vec<u32> = (0..100).rev().collect();
+    assert_eq!(v.as_slice(), &expected[..]);
+
+    for i in (0..100).rev() {
+        assert_eq!(v.remove(0), i);
+    }
+    assert!(v.is_empty());
+
+    let result = std::panic::catch_unwind(|| {
+        let mut v = crate::vector![1u32, 2];
+        v.insert(3, 0);
+    });
+    let msg = result.unwrap_err();
+    let msg = msg.downcast_ref::<String>().unwrap();
+    assert_eq!(msg, "insertion index (is 3) should be <= len (is 2)");
+
+    let result = std::panic::catch_unwind(|| {
+        let mut v = crate::vector![1u32, 2];
+        v.remove(2)
+    });
+    let msg = result.unwrap_err();
+    let msg = msg.downcast_ref::<String>().unwrap();
+    assert_eq!(msg, "removal index (is 2) should be < len (is 2)");
+}
+
+#[test]
+fn map() {
+    let words: Vector<String> = ["hello", "", "shared vector"].iter().map(|s| s.to_string()).collect();
+    let lens = words.map(|s| s.len());
+    assert_eq!(lens.as_slice(), &[5, 0, 13]);
+    assert_eq!(lens.capacity(), 3);
+
+    let empty: Vector<String> = Vector::new();
+    assert!(empty.map(|s| s.len()).is_empty());
+
+    let boxes = crate::vector![Box::new(1u32), Box::new(2)];
+    assert_eq!(boxes.map(|b| *b * 10).as_slice(), &[10, 20]);
+}
+
+#[test]
+fn utf8() {
+    let mut v = Vector::new();
+    v.push_str("héllo");
+    v.push_str("");
+    assert_eq!(v.len(), 6);
+    assert_eq!(v.as_str(), Ok("héllo"));
+    assert_eq!(unsafe { v.as_str_unchecked() }, "héllo");
+
+    v.truncate(2);
+    assert!(v.as_str().is_err());
+    let (bytes, err) = Vector::from_utf8(v).unwrap_err();
+    assert_eq!(err.valid_up_to(), 1);
+    assert_eq!(bytes.as_slice(), &[b'h', 0xc3]);
+
+    let s = Vector::from_utf8(crate::vector![b'o', b'k']).unwrap();
+    assert_eq!(s, "ok");
+}
+
+#[test]
+fn string_conversions() {
+    let v = Vector::from(String::from("shared"));
+    assert_eq!(v.as_slice(), b"shared");
+    assert_eq!(v.capacity(), 6);
+
+    let s: String = v.try_into().unwrap();
+    assert_eq!(s, "shared");
+
+    let invalid = crate::vector![b'a', 0xff];
+    let err = String::try_from(invalid).unwrap_err();
+    assert_eq!(err.as_slice(), &[b'a', 0xff]);
+    assert!(err.into_string().is_err());
+}
+
+#[test]
+fn front_queue() {
+    let mut queue = Vector::new();
+    assert_eq!(queue.pop_front(), None::<Box<u32>>);
+
+    // Enqueue at the back, dequeue at the front.
+    for i in 0..5 {
+        queue.push(Box::new(i));
+    }
+    assert_eq!(queue.pop_front(), Some(Box::new(0)));
+    assert_eq!(queue.pop_front(), Some(Box::new(1)));
+    queue.push(Box::new(5));
+
+    // Requeue an item at the front.
+    queue.push_front(Box::new(10));
+    assert_eq!(queue.as_slice(), &[Box::new(10), Box::new(2), Box::new(3), Box::new(4), Box::new(5)]);
+
+    let mut order = Vec::new();
+    while let Some(item) = queue.pop_front() {
+        order.push(*item);
+    }
+    assert_eq!(order, vec![10, 2, 3, 4, 5]);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn flatten_into() {
+    let nested = crate::vector![
+        crate::vector![Box::new(1u32), Box::new(2)],
+        Vector::new(),
+        crate::vector![Box::new(3)]
+    ];
+    let mut dst = Vector::new();
+    nested.flatten_into(&mut dst);
+    assert_eq!(dst.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+
+    let empty: Vector<Vector<Box<u32>>> = Vector::new();
+    empty.flatten_into(&mut dst);
+    assert_eq!(dst.len(), 3);
+}
+
+#[test]
+fn raw_parts() {
+    // Stands in for a C function that fills a caller-provided buffer.
+    extern "C" fn fill(ptr: *mut u32, len: usize, cap: usize) -> usize {
+        let out = unsafe { core::slice::from_raw_parts_mut(ptr.add(len), cap - len) };
+        for (i, item) in out.iter_mut().enumerate() {
+            *item = i as u32 * 10;
+        }
+        cap
+    }
+
+    let mut v = Vector::with_capacity(4);
+    v.push(7u32);
+    let (ptr, len, cap) = v.into_raw_parts();
+    assert_eq!((len, cap), (1, 4));
+    let len = fill(ptr, len, cap);
+    let mut v = unsafe { Vector::from_raw_parts(ptr, len, cap) };
+    assert_eq!(v.as_slice(), &[7, 0, 10, 20]);
+    v.push(30);
+    assert_eq!(v.len(), 5);
+
+    let (ptr, len, cap) = Vector::<Box<u32>>::new().into_raw_parts();
+    let v = unsafe { Vector::from_raw_parts(ptr, len, cap) };
+    assert!(v.is_empty());
+
+    let mut fixed = crate::FixedVector::from_capacity(2);
+    fixed.try_push(Box::new(1u32)).unwrap();
+    let (ptr, len, cap) = fixed.into_raw_parts();
+    let mut v = unsafe { Vector::from_raw_parts(ptr, len, cap) };
+    v.push(Box::new(2));
+    v.push(Box::new(3));
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+}
+
+#[test]
+fn push_from_iter() {
+    let mut v = crate::vector![Box::new(0u32)];
+    let mut src = (1..6).map(Box::new);
+
+    assert_eq!(v.push_from_iter(&mut src, 2), 2);
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(1), Box::new(2)]);
+    assert!(v.capacity() >= 3);
+
+    assert_eq!(v.push_from_iter(&mut src, 0), 0);
+    assert_eq!(v.push_from_iter(&mut src, 10), 3);
+    assert_eq!(v.len(), 6);
+    assert!(src.next().is_none());
+}
+
+#[test]
+fn push_and_get_ref() {
+    let mut v = Vector::with_capacity(1);
+    **v.push_and_get_ref(Box::new(1u32)) = 10;
+    let item = v.push_and_get_ref(Box::new(2));
+    **item += 1;
+    assert_eq!(v.as_slice(), &[Box::new(10), Box::new(3)]);
+}
+
+#[test]
+fn allocated_bytes() {
+    let v: Vector<u8> = Vector::new();
+    assert_eq!(v.allocated_bytes(), 0);
+    assert!(Vector::<u8>::header_bytes() >= mem::size_of::<Header<DefaultRefCount, Global>>());
+
+    for cap in [1, 100, 5000] {
+        let v: Vector<u32> = Vector::with_capacity(cap);
+        assert_eq!(v.allocated_bytes(), Vector::<u32>::header_bytes() + v.capacity() * 4);
+    }
+
+    // The header is padded so that the items are aligned.
+    #[repr(align(128))]
+    struct Aligned(#[allow(dead_code)] u8);
+    assert_eq!(Vector::<Aligned>::header_bytes(), 128);
+    let v: Vector<Aligned> = Vector::with_capacity(2);
+    assert_eq!(v.allocated_bytes(), 128 * 3);
+
+    // Zero-sized items are never allocated.
+    let v: Vector<()> = Vector::with_capacity(10);
+    assert_eq!(v.allocated_bytes(), 0);
+}
+
+#[test]
+fn cow() {
+    let v = crate::vector![Box::new(1u32), Box::new(2)];
+    let cow: Cow<[Box<u32>]> = (&v).into();
+    assert!(matches!(cow, Cow::Borrowed(_)));
+    let owned: Vector<Box<u32>> = cow.into();
+    assert_eq!(owned, v);
+
+    let cow: Cow<[Box<u32>]> = Cow::Owned(vec![Box::new(3)]);
+    let owned: Vector<Box<u32>> = cow.into();
+    assert_eq!(owned.as_slice(), &[Box::new(3)]);
+
+    let set: std::collections::HashSet<Vector<u32>> = [crate::vector![1, 2]].into_iter().collect();
+    assert!(set.contains(&[1, 2][..]));
+}
+
+#[test]
+fn delete() {
+    let mut v: Vector<Box<u32>> = (0..10).map(Box::new).collect();
+    v.delete(2..5);
+    assert_eq!(v.len(), 7);
+    v.delete(5..);
+    v.delete(..1);
+    v.delete(2..2);
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(5), Box::new(6), Box::new(7)]);
+    v.delete(..);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn remove_item() {
+    let mut v: Vector<Box<u32>> = [1, 2, 3, 2, 1].into_iter().map(Box::new).collect();
+    assert_eq!(v.remove_item(&Box::new(2)), Some(Box::new(2)));
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(3), Box::new(2), Box::new(1)]);
+    assert_eq!(v.remove_item_back(&Box::new(1)), Some(Box::new(1)));
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(3), Box::new(2)]);
+    assert_eq!(v.remove_item(&Box::new(4)), None);
+    assert_eq!(v.remove_item_back(&Box::new(4)), None);
+    assert_eq!(v.len(), 3);
+}
+
+#[test]
+fn batch_push() {
+    let mut v = crate::vector![Box::new(0u32)];
+    v.batch_push((1..4).map(Box::new));
+    assert_eq!(v.as_slice(), &[Box::new(0), Box::new(1), Box::new(2), Box::new(3)]);
+    v.batch_push(Vec::new());
+    assert_eq!(v.len(), 4);
+
+    // An iterator announcing fewer items than it yields.
+    struct Liar(u32);
+    impl Iterator for Liar {
+        type Item = Box<u32>;
+        fn next(&mut self) -> Option<Box<u32>> {
+            self.0 += 1;
+            (self.0 <= 3).then(|| Box::new(self.0))
+        }
+    }
+    impl ExactSizeIterator for Liar {
+        fn len(&self) -> usize { 1 }
+    }
+    let mut v = Vector::new();
+    v.batch_push(Liar(0));
+    assert_eq!(v.as_slice(), &[Box::new(1), Box::new(2), Box::new(3)]);
+}
+
+#[test]
+fn chunks_as_vecs() {
+    let v: Vector<Box<u32>> = (0..10).map(Box::new).collect();
+    let expected = v.clone();
+    let chunks = v.chunks_as_vecs(3);
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+    assert_eq!(chunks.flatten(), expected);
+
+    assert!(Vector::<u32>::new().chunks_as_vecs(4).is_empty());
+    assert_eq!(crate::vector![1u32, 2].chunks_as_vecs(8), vec![crate::vector![1, 2]]);
+}
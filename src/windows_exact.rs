@@ -0,0 +1,86 @@
+use core::iter::FusedIterator;
+
+/// An iterator over overlapping windows of `N` items, yielded as array references.
+///
+/// This struct is created by [`Vector::windows_exact`](crate::Vector::windows_exact).
+/// See its documentation for more.
+#[derive(Debug)]
+pub struct WindowsExact<'a, T, const N: usize> {
+    // The items from the start of the next window to the end of the last one.
+    slice: &'a [T],
+}
+
+impl<'a, T, const N: usize> WindowsExact<'a, T, N> {
+    pub(crate) fn new(slice: &'a [T]) -> Self {
+        assert!(N != 0, "window size must be non-zero");
+        WindowsExact { slice }
+    }
+}
+
+impl<T, const N: usize> Clone for WindowsExact<'_, T, N> {
+    fn clone(&self) -> Self {
+        WindowsExact { slice: self.slice }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for WindowsExact<'a, T, N> {
+    type Item = &'a [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T; N]> {
+        let (window, _) = self.slice.split_first_chunk::<N>()?;
+        self.slice = &self.slice[1..];
+        Some(window)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for WindowsExact<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a [T; N]> {
+        let (_, window) = self.slice.split_last_chunk::<N>()?;
+        self.slice = &self.slice[..self.slice.len() - 1];
+        Some(window)
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for WindowsExact<'_, T, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        (self.slice.len() + 1).saturating_sub(N)
+    }
+}
+
+impl<T, const N: usize> FusedIterator for WindowsExact<'_, T, N> {}
+
+#[test]
+fn windows_exact() {
+    let v = crate::vector![1u32, 2, 3, 4, 5];
+    let mut windows = v.windows_exact::<3>();
+    assert_eq!(windows.len(), 3);
+    assert_eq!(windows.next(), Some(&[1, 2, 3]));
+    assert_eq!(windows.next_back(), Some(&[3, 4, 5]));
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows.next(), Some(&[2, 3, 4]));
+    assert_eq!(windows.next(), None);
+    assert_eq!(windows.next_back(), None);
+
+    assert!(v.windows_exact::<5>().eq([&[1, 2, 3, 4, 5]]));
+    assert_eq!(v.windows_exact::<6>().len(), 0);
+    assert!(v.windows_exact::<1>().map(|w| w[0]).eq(1..6));
+
+    let empty: crate::Vector<u32> = crate::Vector::new();
+    assert_eq!(empty.windows_exact::<2>().next(), None);
+}
+
+#[test]
+#[should_panic]
+fn windows_exact_zero() {
+    let v = crate::vector![1u32, 2];
+    let _ = v.windows_exact::<0>();
+}
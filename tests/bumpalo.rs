@@ -0,0 +1,46 @@
+use bumpalo::Bump;
+use shared_vector::{SharedVector, Vector};
+
+#[test]
+fn bump_push_grow_drop() {
+    let bump = Bump::new();
+
+    let mut v: Vector<String, &Bump> = Vector::new_in(&bump);
+    for i in 0..100 {
+        v.push(i.to_string());
+    }
+    assert_eq!(v.len(), 100);
+    assert!(v.capacity() >= 100);
+    assert_eq!(v[42], "42");
+
+    // Growing reallocates in the arena, the items are moved along.
+    v.reserve(1000);
+    assert_eq!(v[99], "99");
+    v.truncate(10);
+    v.shrink_to_fit();
+    assert_eq!(v.last().map(String::as_str), Some("9"));
+
+    drop(v);
+    assert!(bump.allocated_bytes() > 0);
+}
+
+#[test]
+fn bump_into_shared() {
+    let bump = Bump::new();
+
+    let mut v: Vector<String, &Bump> = Vector::with_capacity_in(4, &bump);
+    v.push("a".to_string());
+    v.push("b".to_string());
+    let ptr = v.as_ptr();
+
+    let a: SharedVector<String, &Bump> = v.into_shared();
+    assert_eq!(a.as_ptr(), ptr);
+    let mut b = a.new_ref();
+    b.push("c".to_string());
+    assert_eq!(a.as_slice(), &["a", "b"]);
+    assert_eq!(b.as_slice(), &["a", "b", "c"]);
+
+    let v = a.into_unique();
+    assert_eq!(v.as_ptr(), ptr);
+    assert_eq!(v.as_slice(), &["a", "b"]);
+}
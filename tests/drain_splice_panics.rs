@@ -0,0 +1,193 @@
+//! Checks that `Drain` and `Splice` neither leak nor double-drop items when an item's destructor
+//! or the replacement iterator panics, for every range, amount of iteration and panic location
+//! in small vectors.
+
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use shared_vector::Vector;
+
+const MAX_ID: usize = 256;
+// Ids of the replacement items in splice tests.
+const NEW_ID: u32 = 100;
+
+thread_local! {
+    static CREATED: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+    static DROPS: RefCell<[u32; MAX_ID]> = const { RefCell::new([0; MAX_ID]) };
+    static PANIC_AT: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+struct Item(u32);
+
+impl Item {
+    fn new(id: u32) -> Self {
+        CREATED.with(|created| created.borrow_mut().push(id));
+        Item(id)
+    }
+}
+
+impl Drop for Item {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.borrow_mut()[self.0 as usize] += 1);
+        if PANIC_AT.get() == Some(self.0) {
+            PANIC_AT.set(None);
+            panic!("boom");
+        }
+    }
+}
+
+fn quiet_panics() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if info.payload().downcast_ref::<&str>() != Some(&"boom") {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn reset(panic_at: Option<u32>) {
+    CREATED.with(|created| created.borrow_mut().clear());
+    DROPS.with(|drops| *drops.borrow_mut() = [0; MAX_ID]);
+    PANIC_AT.set(panic_at);
+}
+
+// Every item that was created must have been dropped exactly once.
+fn check_drops(case: &str) {
+    let created = CREATED.with(|created| created.borrow().clone());
+    DROPS.with(|drops| {
+        let drops = drops.borrow();
+        for (id, count) in drops.iter().enumerate() {
+            let expected = created.contains(&(id as u32)) as u32;
+            assert_eq!(*count, expected, "item {id} dropped {count} times in {case}");
+        }
+    });
+}
+
+fn ids(v: &Vector<Item>) -> Vec<u32> {
+    v.iter().map(|item| item.0).collect()
+}
+
+fn ranges(len: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..=len).flat_map(move |start| (start..=len).map(move |end| (start, end)))
+}
+
+#[test]
+fn drain_panics() {
+    quiet_panics();
+
+    for len in 0..6u32 {
+        for (start, end) in ranges(len as usize) {
+            for consumed in 0..=(end - start) {
+                for panic_at in std::iter::once(None).chain((0..len).map(Some)) {
+                    let case = format!("drain({start}..{end}) of {len} items, {consumed} consumed, panic at {panic_at:?}");
+                    reset(panic_at);
+
+                    let mut v: Vector<Item> = (0..len).map(Item::new).collect();
+                    let drained = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let mut drain = v.drain(start..end);
+                        for _ in 0..consumed {
+                            drop(drain.next());
+                        }
+                    }));
+
+                    // Whether or not a destructor panicked, the items outside of the range are still there.
+                    let expected: Vec<u32> = (0..start as u32).chain(end as u32..len).collect();
+                    let remaining = panic::catch_unwind(AssertUnwindSafe(|| {
+                        assert_eq!(ids(&v), expected, "{case}");
+                        drop(v);
+                    }));
+                    assert!(drained.is_ok() || panic_at.is_some(), "{case}");
+                    if let Err(payload) = remaining {
+                        // Only the armed destructor is allowed to panic.
+                        assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"), "{case}");
+                    }
+                    check_drops(&case);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Panic {
+    None,
+    // When dropping the item with this id.
+    Drop(u32),
+    // When producing the replacement item at this index.
+    Next(u32),
+}
+
+#[test]
+fn splice_panics() {
+    quiet_panics();
+
+    for len in 0..5u32 {
+        for (start, end) in ranges(len as usize) {
+            for replaced in 0..5u32 {
+                for exact_hint in [true, false] {
+                    for consume in [false, true] {
+                        let panics = std::iter::once(Panic::None)
+                            .chain((0..len).chain(NEW_ID..NEW_ID + replaced).map(Panic::Drop))
+                            .chain((0..replaced).map(Panic::Next));
+                        for panic in panics {
+                            let case = format!(
+                                "splice({start}..{end}) of {len} items with {replaced} items, \
+                                 exact hint: {exact_hint}, consume: {consume}, panic: {panic:?}"
+                            );
+                            reset(match panic {
+                                Panic::Drop(id) => Some(id),
+                                _ => None,
+                            });
+
+                            let mut v: Vector<Item> = (0..len).map(Item::new).collect();
+                            let replace_with = (0..replaced).map(move |i| {
+                                if let Panic::Next(at) = panic {
+                                    if at == i {
+                                        panic!("boom");
+                                    }
+                                }
+                                Item::new(NEW_ID + i)
+                            });
+                            let replace_with: Box<dyn Iterator<Item = Item>> = if exact_hint {
+                                Box::new(replace_with)
+                            } else {
+                                // Hide the size hint to exercise the path that collects the remaining items.
+                                Box::new(replace_with.filter(|_| true))
+                            };
+
+                            let spliced = panic::catch_unwind(AssertUnwindSafe(|| {
+                                let mut splice = v.splice(start..end, replace_with);
+                                if consume {
+                                    splice.by_ref().for_each(drop);
+                                }
+                            }));
+
+                            if spliced.is_ok() {
+                                let expected: Vec<u32> = (0..start as u32)
+                                    .chain(NEW_ID..NEW_ID + replaced)
+                                    .chain(end as u32..len)
+                                    .collect();
+                                assert_eq!(ids(&v), expected, "{case}");
+                            } else {
+                                assert!(!matches!(panic, Panic::None), "{case}");
+                                // The items before the range are untouched.
+                                let prefix: Vec<u32> = (0..start as u32).collect();
+                                assert_eq!(&ids(&v)[..start], &prefix[..], "{case}");
+                            }
+
+                            let dropped = panic::catch_unwind(AssertUnwindSafe(|| drop(v)));
+                            if let Err(payload) = dropped {
+                                assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"), "{case}");
+                            }
+                            check_drops(&case);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
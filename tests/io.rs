@@ -0,0 +1,21 @@
+#![cfg(feature = "std")]
+
+use serde::Serialize;
+use shared_vector::Vector;
+
+#[derive(Serialize)]
+struct Point {
+    x: i32,
+    y: i32,
+    name: &'static str,
+}
+
+#[test]
+fn serde_json_to_writer() {
+    let point = Point { x: 1, y: -2, name: "origin" };
+
+    let mut buffer: Vector<u8> = Vector::new();
+    serde_json::to_writer(&mut buffer, &point).unwrap();
+
+    assert_eq!(buffer.as_slice(), br#"{"x":1,"y":-2,"name":"origin"}"#);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use shared_vector::alloc::Global;
+use shared_vector::{vector, AtomicSharedVector, ExactGrowth, SharedVector, Vector};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Doc {
+    ids: Vector<u32>,
+    names: SharedVector<String>,
+    tags: AtomicSharedVector<Vector<u8>>,
+}
+
+fn doc() -> Doc {
+    Doc {
+        ids: vector![1, 2, 3],
+        names: SharedVector::from_slice(&[String::from("a"), String::from("b")]),
+        tags: AtomicSharedVector::from_slice(&[vector![0u8], vector![]]),
+    }
+}
+
+#[test]
+fn json_roundtrip() {
+    let doc = doc();
+
+    let json = serde_json::to_string(&doc).unwrap();
+    assert_eq!(json, r#"{"ids":[1,2,3],"names":["a","b"],"tags":[[0],[]]}"#);
+
+    let back: Doc = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, doc);
+}
+
+#[test]
+fn json_errors() {
+    assert!(serde_json::from_str::<Vector<u32>>("[1, \"a\"]").is_err());
+    assert!(serde_json::from_str::<Vector<u32>>("{}").is_err());
+    assert!(serde_json::from_str::<Vector<u32>>("[]").unwrap().is_empty());
+}
+
+#[test]
+fn json_growth_strategy() {
+    let mut v: Vector<u32, Global, ExactGrowth> = Vector::new_in(Global);
+    v.extend_from_slice(&[4, 5, 6]);
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, "[4,5,6]");
+
+    let back: Vector<u32, Global, ExactGrowth> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.as_slice(), v.as_slice());
+}
+
+#[test]
+fn bincode_roundtrip() {
+    let doc = doc();
+
+    let bytes = bincode::serialize(&doc).unwrap();
+    let back: Doc = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(back, doc);
+
+    // A truncated buffer is an error, not a shorter vector.
+    assert!(bincode::deserialize::<Doc>(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn postcard_roundtrip() {
+    let doc = doc();
+
+    let bytes = postcard::to_allocvec(&doc).unwrap();
+    let back: Doc = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(back, doc);
+
+    // More items than the size hint is allowed to preallocate.
+    let big: Vector<u32> = (0..500_000).collect();
+    let bytes = postcard::to_allocvec(&big).unwrap();
+    let back: Vector<u32> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(back, big);
+}
+
+#[test]
+fn postcard_untrusted_length() {
+    // The varint length prefix claims `u32::MAX` items but no items follow. The size hint
+    // must not be trusted to preallocate the whole vector.
+    let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 0x0F];
+    assert!(postcard::from_bytes::<Vector<u64>>(&bytes).is_err());
+    assert!(postcard::from_bytes::<SharedVector<u64>>(&bytes).is_err());
+    assert!(postcard::from_bytes::<AtomicSharedVector<u64>>(&bytes).is_err());
+}